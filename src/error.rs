@@ -1,8 +1,17 @@
 //! Provides abstract error handling for the Elemental interpreter.
 
+use std::fmt::{
+    self,
+    Display,
+    Formatter,
+};
+
 use colored::*;
 
+use crate::Span;
+
 /// Enumerates the types of errors available to the Elemental interpreter.
+#[derive(Clone, Debug, PartialEq)]
 pub enum Error {
     CouldNotFlushOutput,
     CouldNotReadStdin,
@@ -13,44 +22,169 @@ pub enum Error {
     CouldNotParseNumeric,
     UnexpectedEof,
     CouldNotFindFunction,
+    ExpectedFunction,
     WrongNumberOfArgs,
+    WrongNumberOfOutputs,
     RequiresUnitMatrix,
     SquareMatrixRequired,
+    SingularMatrix,
+    NotPositiveDefinite,
     ExpectedIdentifier,
     ExpectedCloseParen,
+    ExpectedCloseBracket,
+    ExpectedOpenBrace,
+    ExpectedCloseBrace,
+    ExpectedIn,
+    ZeroStep,
+    RangeTooLarge,
+    InvalidIndex,
+    IndexOutOfBounds,
     DividedByZero,
+    DidNotConverge,
+    RootNotBracketed,
+    ComplexNotSupported,
+    IncompatibleUnits,
+    UnitsNotSupported,
+    UncertaintyNotSupported,
+    UnknownUnit (String),
     UndeclaredVariable (String),
     CouldNotReadFile (String),
     CouldNotWriteToFile,
     CouldNotDisplayPlot,
+    Spanned (Box<Error>, Span),
 }
 
 pub use Error::*;
 
+impl Error {
+    /// Attaches a location in the source to this error.
+    /// 
+    /// If the error already has a location, the existing (innermost) location is kept.
+    pub fn with_span(self, span: Span) -> Self {
+        match self {
+            Spanned (_, _) => self,
+            _ if !span.is_known() => self,
+            _ => Spanned (Box::new(self), span),
+        }
+    }
+
+    /// Gets the location in the source of this error, if any.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Spanned (_, span) => Some (*span),
+            _ => None,
+        }
+    }
+
+    /// Gets the underlying error, without any location information.
+    pub fn kind(&self) -> &Error {
+        match self {
+            Spanned (e, _) => e.kind(),
+            _ => self,
+        }
+    }
+
+    /// Renders this error as a diagnostic, showing the offending line of `source`
+    /// with a caret underline beneath the location of the error.
+    /// 
+    /// `origin` is the name of the file the source was read from, if any.
+    pub fn render(&self, source: &str, origin: Option<&str>) -> String {
+        let header = format!("{}: {}", "error".bold().red(), self.to_string().bold());
+
+        let span = match self.span() {
+            Some(s) => s,
+            None => return header,
+        };
+
+        let line = match source.lines().nth(span.line() - 1) {
+            Some(l) => l,
+            None => return header,
+        };
+
+        // Underline to the end of the span, or to the end of the line if the span
+        // covers more than one line
+        let end_col = if span.end_line() == span.line() {
+            span.end_col()
+        } else {
+            line.chars().count() + 1
+        };
+        let width = end_col.saturating_sub(span.col()).max(1);
+
+        let number = span.line().to_string();
+        let gutter = " ".repeat(number.len());
+
+        let location = match origin {
+            Some(o) => format!("{}:{}:{}", o, span.line(), span.col()),
+            None => format!("{}:{}", span.line(), span.col()),
+        };
+
+        format!(
+            "{}\n{}{} {}\n{} {}\n{} {} {}\n{} {} {}{}",
+            header,
+            gutter, "-->".blue().bold(), location,
+            gutter, "|".blue().bold(),
+            number.blue().bold(), "|".blue().bold(), line,
+            gutter, "|".blue().bold(), " ".repeat(span.col() - 1), "^".repeat(width).red().bold(),
+        )
+    }
+}
+
+/// Implementing `std::fmt::Display` allows hosts to report errors however they like.
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let message: String = match self {
+            CouldNotFlushOutput => "could not flush stdout".to_string(),
+            CouldNotReadStdin => "could not read stdin".to_string(),
+            ImproperDimensions => "improper dimensions".to_string(),
+            InvalidOperands => "invalid binary operands".to_string(),
+            InvalidOperator => "invalid operator".to_string(),
+            InvalidValue => "at least one value in this matrix is not a numeric literal".to_string(),
+            CouldNotParseNumeric => "could not parse numeric input".to_string(),
+            UnexpectedEof => "unexpected token or end of token stream".to_string(),
+            CouldNotFindFunction => "could not find function in standard library".to_string(),
+            ExpectedFunction => "expected a function".to_string(),
+            WrongNumberOfArgs => "wrong number of arguments passed to function".to_string(),
+            WrongNumberOfOutputs => "function cannot return this many values".to_string(),
+            RequiresUnitMatrix => "function requires a unit (1x1) matrix".to_string(),
+            SquareMatrixRequired => "function requires a square matrix".to_string(),
+            SingularMatrix => "matrix is singular to working precision".to_string(),
+            NotPositiveDefinite => "matrix is not symmetric positive definite".to_string(),
+            ExpectedIdentifier => "expected identifier".to_string(),
+            ExpectedCloseParen => "expected closing parenthesis".to_string(),
+            ExpectedCloseBracket => "expected closing bracket".to_string(),
+            ExpectedOpenBrace => "expected opening brace".to_string(),
+            ExpectedCloseBrace => "expected closing brace".to_string(),
+            ExpectedIn => "expected `in` after loop variable".to_string(),
+            ZeroStep => "the step of a range cannot be zero".to_string(),
+            RangeTooLarge => "range has too many values".to_string(),
+            InvalidIndex => "matrix indices must be positive integers".to_string(),
+            IndexOutOfBounds => "index exceeds matrix dimensions".to_string(),
+            DividedByZero => "attempted to divide by zero".to_string(),
+            DidNotConverge => "numerical method did not converge".to_string(),
+            RootNotBracketed => "function must change sign over the given interval".to_string(),
+            ComplexNotSupported => "complex numbers are not supported here".to_string(),
+            IncompatibleUnits => "incompatible units".to_string(),
+            UnitsNotSupported => "units are not supported here".to_string(),
+            UncertaintyNotSupported => "uncertain values are not supported here".to_string(),
+            UnknownUnit (s) => format!("unknown unit {}", s),
+            UndeclaredVariable (s) => format!("found undeclared variable {}", s),
+            CouldNotReadFile (s) => format!("could not read file {}", s),
+            CouldNotWriteToFile => "unable to export data to file".to_string(),
+            CouldNotDisplayPlot => "could not display plot in terminal".to_string(),
+            Spanned (e, _) => e.to_string(),
+        };
+
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for Error {}
+
 
 /// Throws errors.
+///
+/// The library never calls this itself; it is provided so that hosts
+/// (such as `eltl`) can report errors in the standard format.
 pub fn throw(error: Error) {
-    let message: String = match error {
-        CouldNotFlushOutput => "could not flush stdout".to_string(),
-        CouldNotReadStdin => "could not read stdin".to_string(),
-        ImproperDimensions => "improper dimensions".to_string(),
-        InvalidOperands => "invalid binary operands".to_string(),
-        InvalidOperator => "invalid operator".to_string(),
-        InvalidValue => "at least one value in this matrix is not a numeric literal".to_string(),
-        CouldNotParseNumeric => "could not parse numeric input".to_string(),
-        UnexpectedEof => "unexpected token or end of token stream".to_string(),
-        CouldNotFindFunction => "could not find function in standard library".to_string(),
-        WrongNumberOfArgs => "wrong number of arguments passed to function".to_string(),
-        RequiresUnitMatrix => "function requires a unit (1x1) matrix".to_string(),
-        SquareMatrixRequired => "function requires a square matrix".to_string(),
-        ExpectedIdentifier => "expected identifier".to_string(),
-        ExpectedCloseParen => "expected closing parenthesis".to_string(),
-        DividedByZero => "attempted to divide by zero".to_string(),
-        UndeclaredVariable (s) => format!("found undeclared variable {}", s),
-        CouldNotReadFile (s) => format!("could not read file {}", s),
-        CouldNotWriteToFile => "unable to export data to file".to_string(),
-        CouldNotDisplayPlot => "could not display plot in terminal".to_string(),
-    };
-
-    println!("{}: {}", "error".bold().red(), message);
-}
\ No newline at end of file
+    println!("{}: {}", "error".bold().red(), error);
+}
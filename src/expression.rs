@@ -2,30 +2,69 @@
 
 use std::{
     fmt::{
+        self,
         Display,
-        Result,
         Formatter,
     },
-    collections::HashMap,
+    collections::{
+        HashMap,
+        HashSet,
+    },
+};
+
+use num_bigint::BigInt;
+use num_complex::Complex64;
+use num_rational::BigRational;
+use num_traits::{
+    ToPrimitive,
+    Zero,
 };
 
 use crate::{
-    standard::get_std_function,
+    standard::{
+        get_std_function,
+        scalar,
+        Invert,
+        Solve,
+        StdFunc,
+    },
     Matrix,
+    Span,
+    Unit,
+    Uncertain,
 };
 
 use crate::error::*;
 
 /// Defines the expression types that are available in Elemental.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Expression {
     Assignment {
         identifier: String,
         value: Box<Expression>,
     },
+    IndexedAssignment {
+        identifier: String,
+        indices: Vec<Expression>,
+        value: Box<Expression>,
+        span: Span,
+    },
+    MultipleAssignment {
+        identifiers: Vec<String>,
+        value: Box<Expression>,
+        span: Span,
+    },
     Identifier (String),
     Int (i64),
     Float (f64),
+    Rational (BigRational),
+    Complex (Complex64),
+    Uncertain (Uncertain),
+    Bool (bool),
+    Quantity {
+        value: Box<Expression>,
+        unit: Unit,
+    },
     Matrix {
         rows: usize,
         cols: usize,
@@ -35,10 +74,61 @@ pub enum Expression {
         left: Box<Expression>,
         op: String,
         right: Box<Expression>,
+        span: Span,
+    },
+    UnaryOp {
+        op: String,
+        operand: Box<Expression>,
+        span: Span,
     },
     Call {
         name: String,
         args: Vec<Expression>,
+        span: Span,
+    },
+    Index {
+        target: Box<Expression>,
+        indices: Vec<Expression>,
+        span: Span,
+    },
+    Range {
+        start: Box<Expression>,
+        step: Option<Box<Expression>>,
+        end: Box<Expression>,
+        span: Span,
+    },
+    Conditional {
+        condition: Box<Expression>,
+        then: Box<Expression>,
+        otherwise: Option<Box<Expression>>,
+        span: Span,
+    },
+    Block {
+        statements: Vec<Expression>,
+    },
+    For {
+        variable: String,
+        iterable: Box<Expression>,
+        body: Box<Expression>,
+        span: Span,
+    },
+    While {
+        condition: Box<Expression>,
+        body: Box<Expression>,
+        span: Span,
+    },
+    Function {
+        params: Vec<String>,
+        body: Box<Expression>,
+    },
+    Lambda {
+        params: Vec<String>,
+        body: Box<Expression>,
+    },
+    Closure {
+        params: Vec<String>,
+        body: Box<Expression>,
+        env: HashMap<String, Expression>,
     },
     Nil,
 }
@@ -47,11 +137,19 @@ pub enum Expression {
 /// using the default formatter.
 impl Display for Expression {
     /// Display each `Expression`.
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Expression::Assignment {
                 identifier: _,
                 value: v,
+            }
+            | Expression::IndexedAssignment {
+                value: v,
+                ..
+            }
+            | Expression::MultipleAssignment {
+                value: v,
+                ..
             } => {
                 write!(f, "{}", v)
             },
@@ -64,6 +162,35 @@ impl Display for Expression {
             Expression::Float (float) => {
                 write!(f, "{:.8}", float)
             },
+            Expression::Rational (q) => {
+                write!(f, "{}", q)
+            },
+            Expression::Complex (z) => {
+                let re = format_part(z.re);
+                let im = format_part(z.im.abs());
+                match (z.re == 0.0, z.im < 0.0) {
+                    (true, false) => write!(f, "{}i", im),
+                    (true, true) => write!(f, "-{}i", im),
+                    (false, false) => write!(f, "{} + {}i", re, im),
+                    (false, true) => write!(f, "{} - {}i", re, im),
+                }
+            },
+            Expression::Uncertain (u) => {
+                write!(f, "{}", u)
+            },
+            Expression::Bool (b) => {
+                write!(f, "{}", b)
+            },
+            Expression::Quantity {
+                value: v,
+                unit: u,
+            } => {
+                // Matrices end with a newline, so their unit goes on the next line
+                match **v {
+                    Expression::Matrix { .. } => write!(f, "{}{}", v, u),
+                    _ => write!(f, "{} {}", v, u),
+                }
+            },
             Expression::Matrix {
                 rows: r,
                 cols: c,
@@ -95,15 +222,106 @@ impl Display for Expression {
                 left: l,
                 op: o,
                 right: r,
+                ..
+            } => {
+                // Parenthesize operands that bind more loosely than this operation,
+                // keeping in mind that `^` and `.^` are right-associative
+                let left = match **l {
+                    Expression::BinOp { op: ref lo, .. } if precedence(lo) < precedence(o)
+                        || (precedence(lo) == precedence(o) && is_power(o)) => format!("({})", l),
+                    _ => format!("{}", l),
+                };
+                let right = match **r {
+                    Expression::BinOp { op: ref ro, .. } if precedence(ro) < precedence(o)
+                        || (precedence(ro) == precedence(o) && !is_power(o)) => format!("({})", r),
+                    _ => format!("{}", r),
+                };
+                write!(f, "{} {} {}", left, o, right)
+            },
+            Expression::UnaryOp {
+                op: o,
+                operand: x,
+                ..
             } => {
-                write!(f, "{} {} {}", l, o, r)
+                match **x {
+                    Expression::BinOp { .. } => write!(f, "{}({})", o, x),
+                    _ => write!(f, "{}{}", o, x),
+                }
             },
             Expression::Call {
-                name: _,
-                args: _,
+                name: n,
+                args: a,
+                ..
+            } => {
+                let args = a.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+                write!(f, "{}({})", n, args.join(", "))
+            },
+            Expression::Index {
+                target: t,
+                indices: i,
+                ..
+            } => {
+                let indices = i.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+                write!(f, "{}[{}]", t, indices.join(", "))
+            },
+            Expression::Range {
+                start: s,
+                step: t,
+                end: e,
+                ..
+            } => {
+                match t {
+                    Some(t) => write!(f, "{}:{}:{}", s, t, e),
+                    None => write!(f, "{}:{}", s, e),
+                }
+            },
+            Expression::Conditional {
+                condition: c,
+                then: t,
+                otherwise: o,
+                ..
+            } => {
+                match o {
+                    Some(o) => write!(f, "if {} {} else {}", c, t, o),
+                    None => write!(f, "if {} {}", c, t),
+                }
+            },
+            Expression::Block {
+                statements: s,
+            } => {
+                let statements = s.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+                write!(f, "{{ {} }}", statements.join("; "))
+            },
+            Expression::For {
+                variable: v,
+                iterable: i,
+                body: b,
+                ..
+            } => {
+                write!(f, "for {} in {} {}", v, i, b)
+            },
+            Expression::While {
+                condition: c,
+                body: b,
+                ..
             } => {
-                unreachable!()
+                write!(f, "while {} {}", c, b)
+            },
+            Expression::Function {
+                params: p,
+                body: b,
             }
+            | Expression::Lambda {
+                params: p,
+                body: b,
+            }
+            | Expression::Closure {
+                params: p,
+                body: b,
+                ..
+            } => {
+                write!(f, "({}) -> {}", p.join(", "), b)
+            },
             Expression::Nil => {
                 write!(f, "")
             },
@@ -113,20 +331,14 @@ impl Display for Expression {
 
 impl Expression {
     /// Simplify this expression, given a reference to a list of variables.
-    pub fn simplify(&self, variables: &mut HashMap<String, Expression>) -> Self {
+    pub fn simplify(&self, variables: &mut HashMap<String, Expression>) -> Result<Self, Error> {
         match self {
             // Look up the variable and plug in
             Expression::Identifier (s) => {
-                let expr = match variables.get(s) {
-                    Some(e) => {
-                        (*e).to_owned().simplify(variables)
-                    },
-                    None => {
-                        self.to_owned()
-                    },
-                };
-
-                expr
+                match variables.get(s) {
+                    Some(e) => e.to_owned().simplify(variables),
+                    None => Err(UndeclaredVariable (s.to_owned())),
+                }
             },
 
             // Insert the assigned variable into the list of variables
@@ -135,108 +347,113 @@ impl Expression {
                 value: ref v,
             } => {
                 // Simplify the value of assignment
-                let simplified = (**v).simplify(variables);
+                let mut simplified = (**v).simplify(variables)?;
+
+                // A function definition never captures an older value of its
+                // own name, so it is bound to itself when called
+                if let (Expression::Function { .. }, Expression::Closure { env, .. }) = (&**v, &mut simplified) {
+                    env.remove(i);
+                }
 
                 // Register the variable
                 variables.insert(i.to_owned(), simplified.to_owned());
 
                 // Return the simplified value
-                simplified.to_owned()
+                Ok(simplified)
             }
 
+            // Replace the indexed values of an existing variable
+            Expression::IndexedAssignment {
+                identifier: i,
+                indices: n,
+                value: v,
+                span,
+            } => {
+                Self::simplify_indexed_assignment(i, n, v, variables).map_err(|e| e.with_span(*span))
+            },
+
+            // Assign each value returned by a function to its own variable
+            Expression::MultipleAssignment {
+                identifiers: i,
+                value: v,
+                span,
+            } => {
+                Self::simplify_multiple_assignment(i, v, variables).map_err(|e| e.with_span(*span))
+            },
+
             // Simplify the left and right and return
             Expression::BinOp {
                 left: l,
                 op: o,
                 right: r,
+                span,
             } => {
-                // Simplify the left-hand and right-hand sides
-                let left = l.simplify(variables);
-                let right = r.simplify(variables);
-
-                if let Expression::Int (l) = left {
-                    if let Expression::Int (r) = right {
-                        // Evaluate this as a float, then try to cast it to an `Int`
-                        let f = binop(l as f64, r as f64, &o);
-                        if f.fract() == 0.0 {
-                            Expression::Int (f as i64)
-                        } else {
-                            Expression::Float (f)
-                        }
-                    } else if let Expression::Float (r) = right {
-                        let left_float = l as f64;
-                        Expression::Float (binop(left_float, r, &o))
-                    } else if let Expression::Matrix {
-                        rows: r,
-                        cols: c,
-                        values: v,
-                    } = right {
-                        let mut values = Vec::new();
-
-                        for val in v {
-                            values.push(Expression::BinOp {
-                                left: Box::new(left.to_owned()),
-                                op: "*".to_string(),
-                                right: Box::new(val),
-                            }.simplify(variables)); 
-                        }
+                Self::simplify_binop(l, o, r, *span, variables).map_err(|e| e.with_span(*span))
+            },
+            
+            // Simplify the operand and apply the operation
+            Expression::UnaryOp {
+                op: o,
+                operand: x,
+                span,
+            } => {
+                Self::simplify_unary(o, x, variables).map_err(|e| e.with_span(*span))
+            },
 
-                        Expression::Matrix {
-                            rows: r,
-                            cols: c,
-                            values,
-                        }
-                    } else {
-                        Expression::BinOp {
-                            left: Box::new(Expression::Int (l)),
-                            op: o.to_owned(),
-                            right: r.to_owned(),
-                        }
-                    }
-                } else if let Expression::Float (l) = left {
-                    if let Expression::Int (r) = right {
-                        let right_float = r as f64;
-                        Expression::Float (binop(l, right_float, &o))
-                    } else if let Expression::Float (r) = right {
-                        Expression::Float (binop(l, r, &o))
-                    } else {
-                        throw(InvalidOperands);
-                        return Expression::Nil;
-                    }
-                } else if let Expression::Matrix {
-                    rows: r,
-                    cols: k1,
-                    values: vl,
-                } = left {
-                    if let Expression::Matrix {
-                        rows: k2,
-                        cols: c,
-                        values: vr,
-                    } = right {
-                        if k1 != k2 {
-                            throw(ImproperDimensions);
-                            return Expression::Nil;
-                        }
+            // `Int` and `Bool` are already in simplest form
+            Expression::Int (_) | Expression::Bool (_) => Ok(self.to_owned()),
 
-                        matrix_dot(vl, vr, r, c, k1)
-                    } else {
-                        throw(InvalidOperands);
-                        return Expression::Nil;
-                    }
+            // `Float` can be reduced to `Int` if it has no fractional part
+            Expression::Float (f) => {
+                if f.fract() == 0.0 && f.abs() < i64::MAX as f64 {
+                    Ok(Expression::Int (*f as i64))
                 } else {
-                    return self.to_owned();
+                    Ok(Expression::Float (*f))
                 }
             },
-            
-            // `Int is already in simplest form
-            Expression::Int (_) => self.to_owned(),
 
-            // `Float` can be reduced to `Int` if it has no fractional part
-            Expression::Float (f) => {
-                if f.fract() == 0.0 {
-                    Expression::Int (*f as i64)
+            // Dimensionless quantities (like `km/m`) are reduced to numbers
+            Expression::Quantity {
+                value: v,
+                unit: u,
+            } => {
+                let value = v.simplify(variables)?;
+
+                if !u.is_dimensionless() {
+                    Ok(Expression::Quantity {
+                        value: Box::new(value),
+                        unit: u.to_owned(),
+                    })
+                } else if u.scale() == 1.0 {
+                    Ok(value)
+                } else {
+                    scale(value, u.scale())
+                }
+            },
+
+            // `Rational` can be reduced to `Int` if it is a (small enough) integer
+            Expression::Rational (q) => {
+                match (q.is_integer(), q.to_integer().to_i64()) {
+                    (true, Some(i)) => Ok(Expression::Int (i)),
+                    _ => Ok(self.to_owned()),
+                }
+            },
+
+            // `Uncertain` can be reduced to `Float` if it has no uncertainty
+            Expression::Uncertain (u) => {
+                if u.deviation() == 0.0 {
+                    Expression::Float (u.nominal()).simplify(variables)
+                } else {
+                    Ok(self.to_owned())
+                }
+            },
+
+            // `Complex` can be reduced to `Float` if it has no imaginary part
+            Expression::Complex (z) => {
+                if z.im == 0.0 {
+                    Expression::Float (z.re).simplify(variables)
                 } else {
-                    Expression::Float (*f)
+                    Ok(Expression::Complex (*z))
                 }
             },
             
@@ -249,132 +466,1600 @@ impl Expression {
                 let mut new = Vec::new();
 
                 for val in v {
-                    new.push(val.simplify(variables));
+                    new.push(val.simplify(variables)?);
+                }
+
+                // Matrices inside a matrix, like the range in `[0 1:3]`, are concatenated
+                if new.iter().any(|x| matches!(x, Expression::Matrix { .. })) {
+                    return concatenate(*r, *c, new)?.simplify(variables);
                 }
 
                 if *r == 1 && *c == 1 {
-                    v[0].simplify(variables).to_owned()
+                    Ok(new.remove(0))
                 } else {
-                    Expression::Matrix {
+                    Ok(Expression::Matrix {
                         rows: *r,
                         cols: *c,
                         values: new,
-                    }
+                    })
                 }
             },
 
             // To simplify a call, look up the function in the standard library
             // and pass the arguments necessary
-            // 
-            // In Elemental, all functions act on matrices.  
             Expression::Call {
                 name: n,
                 args: a,
+                span,
             } => {
-                // Simplify each argument and convert them to "native" matrices.
-                let mut args = Vec::<Matrix>::new();
-                for arg in a {
-                    let simplified = arg.simplify(variables);
-                    if let Expression::Matrix {
-                        rows: r,
-                        cols: c,
-                        values: v,
-                    } = simplified {
-                        // Convert each value in the matrix from `Expression` to `f64`.
-                        let mut values: Vec<f64> = Vec::new();
-                        for value in v {
-                            if let Self::Int (i) = value {
-                                values.push(i as f64);
-                            } else if let Self::Float (f) = value {
-                                values.push(f);
-                            } else {
-                                // A value in one of the matrices is not a numeric literal
-                                throw(InvalidValue);
-                                return Expression::Nil;
-                            }
-                        }
-                        args.push(Matrix::new(r, c, values));
-                    } else if let Expression::Int (i) = simplified {
-                        // If one value is a number, convert it into a 1x1 matrix
-                        args.push(Matrix::new(1, 1, vec![i as f64]));
-                    } else if let Expression::Float (f) = simplified {
-                        // If one value is a number, convert it into a 1x1 matrix
-                        args.push(Matrix::new(1, 1, vec![f]));
-                    } else {
-                        // One of the arguments is not a matrix or a number
-                        throw(InvalidOperands);
-                    }
+                Self::simplify_call(n, a, variables).map_err(|e| e.with_span(*span))
+            },
+
+            // To simplify an index, select the given values of the target
+            Expression::Index {
+                target: t,
+                indices: i,
+                span,
+            } => {
+                Self::simplify_index(t, i, variables).map_err(|e| e.with_span(*span))
+            },
+
+            // A range becomes a row vector
+            Expression::Range {
+                start: s,
+                step: t,
+                end: e,
+                span,
+            } => {
+                Self::simplify_range(s, t.as_deref(), e, variables).map_err(|e| e.with_span(*span))
+            },
+            
+            // Only the branch that is taken is simplified, in the enclosing scope,
+            // so that `if c { y = 1 } else { y = 2 }` declares `y`
+            Expression::Conditional {
+                condition: c,
+                then: t,
+                otherwise: o,
+                span,
+            } => {
+                let condition = c.simplify(variables).and_then(|x| x.to_condition()).map_err(|e| e.with_span(*span))?;
+
+                match (condition, o) {
+                    (true, _) => Self::simplify_branch(t, variables),
+                    (false, Some(o)) => Self::simplify_branch(o, variables),
+                    (false, None) => Ok(Expression::Nil),
                 }
+            },
 
-                let stdfn = get_std_function(n.to_owned());
-                let output_matrix = stdfn.eval(args);
+            // Blocks are simplified in their own scope, but may update existing variables
+            Expression::Block {
+                statements: s,
+            } => {
+                let mut scope = variables.to_owned();
+                let mut result = Expression::Nil;
+                for statement in s {
+                    result = statement.simplify(&mut scope)?;
+                }
+                update(variables, scope);
 
-                let values = output_matrix.copy_vals().iter().map(|x| Self::Float (*x)).collect::<Vec<Self>>();
+                Ok(result)
+            },
 
-                Self::Matrix {
-                    rows: output_matrix.rows(),
-                    cols: output_matrix.cols(),
-                    values,
-                }.simplify(variables)
+            // Loops are simplified for their effect on variables, and have no value
+            Expression::For {
+                variable: v,
+                iterable: i,
+                body: b,
+                span,
+            } => {
+                Self::simplify_for(v, i, b, variables).map_err(|e| e.with_span(*span))
             },
-            
+            Expression::While {
+                condition: c,
+                body: b,
+                span,
+            } => {
+                loop {
+                    let condition = c.simplify(variables).and_then(|x| x.to_condition()).map_err(|e| e.with_span(*span))?;
+                    if !condition {
+                        break;
+                    }
+                    b.simplify(variables)?;
+                }
+
+                Ok(Expression::Nil)
+            },
+
+            // Functions and lambdas capture the variables they use when they are evaluated
+            Expression::Function {
+                params: p,
+                body: b,
+            }
+            | Expression::Lambda {
+                params: p,
+                body: b,
+            } => Ok(Expression::Closure {
+                params: p.to_owned(),
+                body: b.to_owned(),
+                env: capture(b, p, variables),
+            }),
+            Expression::Closure { .. } => Ok(self.to_owned()),
+
             // `Nil` is already in simplest form
-            Expression::Nil => self.to_owned(),
+            Expression::Nil => Ok(self.to_owned()),
         }
     }
-}
-
 
-/// Executes the given binary operation on two floats.
-pub fn binop(x: f64, y: f64, binop: &str) -> f64 {
-    match binop {
-        "+" => x + y,
-        "-" => x - y,
-        "*" => x * y,
-        "/" => {
-            if y == 0.0 {
-                throw(DividedByZero);
-                0.0
-            } else {
-                x / y
+    /// Simplify a binary operation.
+    /// 
+    /// Operations between a scalar and a matrix are applied to each element of the
+    /// matrix.  Between two matrices, `*` is the matrix product and `/` multiplies by
+    /// the inverse of the right-hand side, while every other operator is element-wise.
+    /// `A \ b` solves the linear system `Ax = b`.
+    /// 
+    /// `&&` and `||` only evaluate their right-hand side if their left-hand side
+    /// is a scalar that does not already decide the result.
+    fn simplify_binop(l: &Expression, o: &str, r: &Expression, span: Span, variables: &mut HashMap<String, Expression>) -> Result<Self, Error> {
+        // Simplify the left-hand and right-hand sides
+        let left = l.simplify(variables)?;
+        if left.is_scalar() {
+            match (o, left.to_bool()?) {
+                ("&&", false) => return Ok(Expression::Bool (false)),
+                ("||", true) => return Ok(Expression::Bool (true)),
+                _ => (),
             }
-        },
-        _ => {
-            throw(InvalidOperator);
-            0.0
-        },
+        }
+        let right = r.simplify(variables)?;
+
+        if matches!(left, Expression::Quantity { .. }) || matches!(right, Expression::Quantity { .. }) {
+            return Self::simplify_quantity_binop(left, o, right, span);
+        }
+
+        // `A \ b` solves `Ax = b`
+        if o == "\\" {
+            return Solve.call(vec![left, right], variables);
+        }
+
+        match (left, right) {
+            (left, right) if left.is_scalar() && right.is_scalar() => scalar_binop(left, o, right),
+
+            // Broadcast a scalar over a matrix
+            (left, Expression::Matrix {
+                rows: r,
+                cols: c,
+                values: v,
+            }) if left.is_scalar() => {
+                if o == "^" {
+                    return Err(InvalidOperands);
+                }
+
+                let values = vec![left; v.len()];
+                elementwise(values, v, r, c, o, span)
+            },
+            (Expression::Matrix {
+                rows: r,
+                cols: c,
+                values: v,
+            }, Expression::Int (n)) if o == "^" => {
+                if r != c {
+                    return Err(SquareMatrixRequired);
+                }
+
+                matrix_power(v, r, n, span)
+            },
+            (Expression::Matrix {
+                rows: r,
+                cols: c,
+                values: v,
+            }, right) if right.is_scalar() => {
+                if o == "^" {
+                    return Err(InvalidOperands);
+                }
+
+                let values = vec![right; v.len()];
+                elementwise(v, values, r, c, o, span)
+            },
+
+            (Expression::Matrix {
+                rows: r1,
+                cols: c1,
+                values: vl,
+            }, Expression::Matrix {
+                rows: r2,
+                cols: c2,
+                values: vr,
+            }) => match o {
+                "*" => {
+                    if c1 != r2 {
+                        return Err(ImproperDimensions);
+                    }
+
+                    matrix_dot(vl, vr, r1, c2, c1, span)
+                },
+                "/" => {
+                    if r2 != c2 {
+                        return Err(SquareMatrixRequired);
+                    }
+                    if c1 != r2 {
+                        return Err(ImproperDimensions);
+                    }
+
+                    // Multiply by the inverse of the right-hand side
+                    let inverse = match matrix_power(vr, r2, -1, span)? {
+                        Expression::Matrix { values: v, .. } => v,
+                        v => vec![v],
+                    };
+                    matrix_dot(vl, inverse, r1, c2, c1, span)?.simplify(&mut HashMap::new())
+                },
+                "^" => Err(InvalidOperands),
+                _ => {
+                    if r1 != r2 || c1 != c2 {
+                        return Err(ImproperDimensions);
+                    }
+
+                    elementwise(vl, vr, r1, c1, o, span)
+                },
+            },
+            _ => Err(InvalidOperands),
+        }
     }
-}
 
+    /// Simplify a binary operation on quantities with units.
+    /// 
+    /// Units are multiplied and divided along with their values, and products that
+    /// form a coherent derived unit are named (so `kg*m/s^2` is in `N`).  Quantities
+    /// must have the same dimension to be added, subtracted or compared, in
+    /// which case the right-hand side is converted into the units of the left.
+    fn simplify_quantity_binop(left: Expression, o: &str, right: Expression, span: Span) -> Result<Self, Error> {
+        let (lv, lu) = left.to_quantity();
+        let (rv, ru) = right.to_quantity();
+        let operate = |l: Expression, r: Expression| Expression::BinOp {
+            left: Box::new(l),
+            op: o.to_string(),
+            right: Box::new(r),
+            span,
+        }.simplify(&mut HashMap::new());
 
-/// Computes the dot product of two matrices.
-pub fn matrix_dot(left: Vec<Expression>, right: Vec<Expression>, rows: usize, cols: usize, count: usize) -> Expression {
-    let mut values = Vec::new();
-    for i in 0..rows {
-        for j in 0..cols {
-            let mut cell = Expression::Int (0);
-            for k in 0..count {
-                // Add the addend to the cell
-                let addend = Expression::BinOp {
-                    left: Box::new(left[i*count + k].to_owned()),
-                    right: Box::new(right[k*cols + j].to_owned()),
-                    op: "*".to_string(),
-                };
+        let unit = match o {
+            "*" | ".*" => lu.multiply(&ru).derived(),
+            "/" | "./" => lu.divide(&ru).derived(),
+            "\\" => ru.divide(&lu).derived(),
+            "^" | ".^" => match rv {
+                Expression::Int (n) if ru.is_dimensionless() && n.abs() <= i32::MAX as i64 => lu.pow(n as i32),
+                _ => return Err(IncompatibleUnits),
+            },
+            "+" | "-" | "±" | "==" | "!=" | "<" | ">" | "<=" | ">=" => {
+                if lu.dimension() != ru.dimension() {
+                    return Err(IncompatibleUnits);
+                }
 
-                cell = Expression::BinOp {
-                    left: Box::new(cell),
-                    right: Box::new(addend),
-                    op: "+".to_string(),
+                // Convert the right-hand side into the units of the left
+                let rv = scale(rv, ru.scale()/lu.scale())?;
+                let value = operate(lv, rv)?;
+
+                return match o {
+                    "+" | "-" | "±" => Expression::Quantity {
+                        value: Box::new(value),
+                        unit: lu,
+                    }.simplify(&mut HashMap::new()),
+                    _ => Ok(value),
                 };
-            }
-            // Push the cell to the list of values
-            values.push(cell.simplify(&mut HashMap::new()));
+            },
+            _ => return Err(InvalidOperands),
+        };
+
+        Expression::Quantity {
+            value: Box::new(operate(lv, rv)?),
+            unit,
+        }.simplify(&mut HashMap::new())
+    }
+
+    /// Simplify a unary operation.
+    fn simplify_unary(o: &str, x: &Expression, variables: &mut HashMap<String, Expression>) -> Result<Self, Error> {
+        let operand = x.simplify(variables)?;
+
+        match (o, operand) {
+            ("+", operand @ (Expression::Int (_) | Expression::Float (_) | Expression::Rational (_) | Expression::Complex (_) | Expression::Uncertain (_) | Expression::Matrix { .. })) => Ok(operand),
+            ("+" | "-", Expression::Bool (b)) => Self::simplify_unary(o, &Expression::Int (b as i64), variables),
+            ("!", operand) if operand.is_scalar() => Ok(Expression::Bool (!operand.to_bool()?)),
+            ("-", Expression::Int (i)) => match i.checked_neg() {
+                Some(n) => Ok(Expression::Int (n)),
+                None => Ok(Expression::Rational (-BigRational::from_integer(BigInt::from(i)))),
+            },
+            ("-", Expression::Float (f)) => Ok(Expression::Float (-f)),
+            ("-", Expression::Rational (q)) => Expression::Rational (-q).simplify(variables),
+            ("-", Expression::Complex (z)) => Ok(Expression::Complex (-z)),
+            ("-", Expression::Uncertain (u)) => Ok(Expression::Uncertain (-u)),
+            ("-", Expression::Quantity {
+                value: v,
+                unit: u,
+            }) => Ok(Expression::Quantity {
+                value: Box::new(Self::simplify_unary(o, &v, variables)?),
+                unit: u,
+            }),
+            ("+", operand @ Expression::Quantity { .. }) => Ok(operand),
+            ("-" | "!", Expression::Matrix {
+                rows: r,
+                cols: c,
+                values: v,
+            }) => {
+                let mut values = Vec::new();
+                for value in v {
+                    values.push(Self::simplify_unary(o, &value, variables)?);
+                }
+
+                Ok(Expression::Matrix {
+                    rows: r,
+                    cols: c,
+                    values,
+                })
+            },
+            ("+" | "-" | "!", _) => Err(InvalidOperands),
+            _ => Err(InvalidOperator),
         }
     }
 
-    Expression::Matrix {
-        rows,
-        cols,
-        values,
+    /// Simplify a `for` loop, binding the variable to each column of the
+    /// iterable in turn.  For a row vector, this is each value.
+    /// 
+    /// The loop variable is only visible inside the loop.
+    fn simplify_for(v: &str, i: &Expression, b: &Expression, variables: &mut HashMap<String, Expression>) -> Result<Self, Error> {
+        let (rows, cols, values) = dimensions(i.simplify(variables)?)?;
+
+        for j in 0..cols {
+            let column = Expression::Matrix {
+                rows,
+                cols: 1,
+                values: (0..rows).map(|k| values[k*cols + j].to_owned()).collect(),
+            }.simplify(variables)?;
+
+            let mut scope = variables.to_owned();
+            scope.insert(v.to_owned(), column);
+            b.simplify(&mut scope)?;
+            scope.remove(v);
+            update(variables, scope);
+        }
+
+        Ok(Expression::Nil)
+    }
+
+    /// Simplify a branch of a conditional.
+    /// 
+    /// Unlike other blocks, the block of a branch does not have its own scope.
+    fn simplify_branch(b: &Expression, variables: &mut HashMap<String, Expression>) -> Result<Self, Error> {
+        match b {
+            Expression::Block {
+                statements: s,
+            } => {
+                let mut result = Expression::Nil;
+                for statement in s {
+                    result = statement.simplify(variables)?;
+                }
+
+                Ok(result)
+            },
+            _ => b.simplify(variables),
+        }
+    }
+
+    /// Apply this closure to a list of (simplified) arguments.
+    /// 
+    /// The body of the closure is evaluated in its own scope, so parameters
+    /// and assignments inside it do not affect the caller's variables.  The
+    /// body only sees the variables that were captured when it was created.
+    pub fn apply(&self, args: Vec<Expression>) -> Result<Self, Error> {
+        let (params, body, env) = match self {
+            Expression::Closure {
+                params: p,
+                body: b,
+                env: e,
+            } => (p, b, e),
+            _ => return Err(ExpectedFunction),
+        };
+
+        if params.len() != args.len() {
+            return Err(WrongNumberOfArgs);
+        }
+
+        // Bind each parameter in a new scope
+        let mut scope = env.clone();
+        for (param, arg) in params.iter().zip(args) {
+            scope.insert(param.to_owned(), arg);
+        }
+
+        body.simplify(&mut scope)
     }
-}
\ No newline at end of file
+
+    /// Simplify a call to a function.
+    /// 
+    /// User-defined functions and closures are looked up first, then the standard library.
+    /// A function that did not capture its own name is bound to it, so that it can recurse.
+    fn simplify_call(n: &str, a: &[Expression], variables: &mut HashMap<String, Expression>) -> Result<Self, Error> {
+        match variables.get(n) {
+            Some(Expression::Closure {
+                params: p,
+                body: b,
+                env: e,
+            }) => {
+                let mut env = e.to_owned();
+                if !env.contains_key(n) {
+                    env.insert(n.to_owned(), variables[n].to_owned());
+                }
+                let function = Expression::Closure {
+                    params: p.to_owned(),
+                    body: b.to_owned(),
+                    env,
+                };
+                let args = Self::simplify_args(a, None, variables)?;
+                function.apply(args)
+            },
+            _ => {
+                let stdfn = get_std_function(n.to_owned());
+                let args = Self::simplify_args(a, stdfn.as_deref().ok(), variables)?;
+                stdfn?.call(args, variables)
+            },
+        }
+    }
+
+    /// Simplify the arguments of a call to a function.
+    /// 
+    /// Arguments of a standard function that name units (like `ft` in `to(x, ft)`)
+    /// are read as units, unless they are variables.
+    fn simplify_args(a: &[Expression], stdfn: Option<&dyn StdFunc>, variables: &mut HashMap<String, Expression>) -> Result<Vec<Self>, Error> {
+        let units = stdfn.map_or(Vec::new(), |f| f.unit_args());
+
+        let mut args = Vec::new();
+        for (k, arg) in a.iter().enumerate() {
+            match units.contains(&k).then(|| arg.to_unit(variables)).flatten() {
+                Some(unit) => args.push(Expression::Quantity {
+                    value: Box::new(Expression::Int (1)),
+                    unit,
+                }),
+                None => args.push(arg.simplify(variables)?),
+            }
+        }
+
+        Ok(args)
+    }
+
+    /// Reads this expression as a unit, like `ft` or `km/h`, if it is made of
+    /// units (with integer powers) that are not variables.
+    fn to_unit(&self, variables: &HashMap<String, Expression>) -> Option<Unit> {
+        match self {
+            Expression::Identifier (s) if !variables.contains_key(s) => Unit::parse(s),
+            Expression::Int (1) => Some (Unit::none()),
+            Expression::BinOp {
+                left: l,
+                op: o,
+                right: r,
+                ..
+            } => {
+                let left = l.to_unit(variables)?;
+                match (o.as_str(), r.as_ref()) {
+                    ("*", r) => Some (left.multiply(&r.to_unit(variables)?)),
+                    ("/", r) => Some (left.divide(&r.to_unit(variables)?)),
+                    ("^", Expression::Int (n)) => Some (left.pow(i32::try_from(*n).ok()?)),
+                    ("^", Expression::UnaryOp { op, operand, .. }) if op == "-" => match operand.as_ref() {
+                        Expression::Int (n) => Some (left.pow(-i32::try_from(*n).ok()?)),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// Simplify an index into a matrix.
+    /// 
+    /// With two indices, the given rows and columns are selected.  With one index,
+    /// values are selected in row-major order.  Indices are counted from 1.
+    fn simplify_index(t: &Expression, i: &[Expression], variables: &mut HashMap<String, Expression>) -> Result<Self, Error> {
+        let target = t.simplify(variables)?;
+
+        // Indexing a quantity selects from its value, keeping its units
+        if let Expression::Quantity { value: v, unit: u } = target {
+            return Ok(Expression::Quantity {
+                value: Box::new(Self::simplify_index(&v, i, variables)?),
+                unit: u,
+            });
+        }
+
+        let (rows, cols, values) = dimensions(target)?;
+        let (selection, shape) = select(rows, cols, i, variables)?;
+
+        Expression::Matrix {
+            rows: shape.0,
+            cols: shape.1,
+            values: selection.into_iter().map(|k| values[k].to_owned()).collect(),
+        }.simplify(variables)
+    }
+
+    /// Simplify an assignment to the given indices of a variable, returning
+    /// the new value of the variable.
+    /// 
+    /// A scalar is assigned to every selected value, while a matrix must
+    /// have as many values as are selected.
+    fn simplify_indexed_assignment(i: &str, n: &[Expression], v: &Expression, variables: &mut HashMap<String, Expression>) -> Result<Self, Error> {
+        let current = match variables.get(i) {
+            Some(e) => e.to_owned().simplify(variables)?,
+            None => return Err(UndeclaredVariable (i.to_owned())),
+        };
+        let (rows, cols, mut values) = dimensions(current)?;
+        let (selection, _) = select(rows, cols, n, variables)?;
+
+        let replacement = match v.simplify(variables)? {
+            Expression::Matrix {
+                values: r,
+                ..
+            } => {
+                if r.len() != selection.len() {
+                    return Err(ImproperDimensions);
+                }
+                r
+            },
+            scalar @ (Expression::Int (_) | Expression::Float (_) | Expression::Rational (_) | Expression::Complex (_) | Expression::Uncertain (_)) => vec![scalar; selection.len()],
+            _ => return Err(InvalidOperands),
+        };
+
+        for (k, value) in selection.into_iter().zip(replacement) {
+            values[k] = value;
+        }
+
+        let simplified = Expression::Matrix {
+            rows,
+            cols,
+            values,
+        }.simplify(variables)?;
+
+        // Register the variable
+        variables.insert(i.to_owned(), simplified.to_owned());
+
+        Ok(simplified)
+    }
+
+    /// Simplify an assignment of several values at once, as in `[Q, R] = qr(A)`.
+    /// 
+    /// Only standard functions can return more than one value.  Multiple
+    /// assignments have no value of their own.
+    fn simplify_multiple_assignment(i: &[String], v: &Expression, variables: &mut HashMap<String, Expression>) -> Result<Self, Error> {
+        let is_function = |n: &String| matches!(variables.get(n), Some(Expression::Closure { .. }));
+
+        let values = match v {
+            Expression::Call {
+                name: n,
+                args: a,
+                span,
+            } if !is_function(n) => {
+                let stdfn = get_std_function(n.to_owned()).map_err(|e| e.with_span(*span))?;
+                let args = Self::simplify_args(a, Some(stdfn.as_ref()), variables)?;
+
+                stdfn.call_multiple(args, i.len(), variables).map_err(|e| e.with_span(*span))?
+            },
+            _ if i.len() == 1 => vec![v.simplify(variables)?],
+            _ => return Err(WrongNumberOfOutputs),
+        };
+
+        if values.len() != i.len() {
+            return Err(WrongNumberOfOutputs);
+        }
+
+        for (identifier, value) in i.iter().zip(values) {
+            variables.insert(identifier.to_owned(), value);
+        }
+
+        Ok(Expression::Nil)
+    }
+
+    /// Simplify a range into a row vector, counting from `s` by `t` (or by 1)
+    /// up to and including `e`.
+    /// 
+    /// The end is included if it is within a small tolerance of a step, so that
+    /// ranges like `0:0.1:1` include `1`.  A range that never reaches its end is empty.
+    fn simplify_range(s: &Expression, t: Option<&Expression>, e: &Expression, variables: &mut HashMap<String, Expression>) -> Result<Self, Error> {
+        let start = scalar(&s.simplify(variables)?)?;
+        let step = match t {
+            Some(t) => scalar(&t.simplify(variables)?)?,
+            None => 1.0,
+        };
+        let end = scalar(&e.simplify(variables)?)?;
+
+        if step == 0.0 {
+            return Err(ZeroStep);
+        }
+
+        // Count the steps, forgiving rounding error in the step
+        let steps = (end - start)/step;
+        let count = if steps > -RANGE_TOLERANCE {
+            (steps + RANGE_TOLERANCE).floor() + 1.0
+        } else {
+            0.0
+        };
+
+        // Refuse ranges that could not fit in memory before allocating them
+        if count > MAX_RANGE_LENGTH as f64 {
+            return Err(RangeTooLarge);
+        }
+        let count = count as usize;
+
+        let mut values = Vec::new();
+        for k in 0..count {
+            values.push(Expression::Float (start + (k as f64)*step).simplify(variables)?);
+        }
+
+        Ok(Expression::Matrix {
+            rows: 1,
+            cols: values.len(),
+            values,
+        })
+    }
+
+    /// Checks whether or not this expression is a scalar (a number or a boolean).
+    pub fn is_scalar(&self) -> bool {
+        matches!(self, Expression::Int (_) | Expression::Float (_) | Expression::Rational (_) | Expression::Complex (_) | Expression::Uncertain (_) | Expression::Bool (_))
+    }
+
+    /// Checks whether or not this expression is exact (an integer, a rational
+    /// number or a boolean), or a matrix of exact values.
+    pub fn is_exact(&self) -> bool {
+        match self {
+            Expression::Int (_) | Expression::Rational (_) | Expression::Bool (_) => true,
+            Expression::Matrix { values: v, .. } => v.iter().all(|x| x.is_exact()),
+            _ => false,
+        }
+    }
+
+    /// Checks whether or not this expression is an uncertain value, or a matrix
+    /// containing one.
+    pub fn is_uncertain(&self) -> bool {
+        match self {
+            Expression::Uncertain (_) => true,
+            Expression::Matrix { values: v, .. } => v.iter().any(|x| x.is_uncertain()),
+            _ => false,
+        }
+    }
+
+    /// Checks whether or not this expression is a complex number, or a matrix
+    /// containing one.
+    pub fn is_complex(&self) -> bool {
+        match self {
+            Expression::Complex (_) => true,
+            Expression::Matrix { values: v, .. } => v.iter().any(|x| x.is_complex()),
+            _ => false,
+        }
+    }
+
+    /// Converts this scalar into a boolean.
+    /// 
+    /// Numbers are true if they are nonzero.
+    pub fn to_bool(&self) -> Result<bool, Error> {
+        match self {
+            Expression::Bool (b) => Ok(*b),
+            Expression::Int (i) => Ok(*i != 0),
+            Expression::Float (f) => Ok(*f != 0.0),
+            Expression::Rational (q) => Ok(!q.is_zero()),
+            Expression::Complex (z) => Ok(*z != Complex64::new(0.0, 0.0)),
+            Expression::Uncertain (u) => Ok(u.nominal() != 0.0),
+            _ => Err(InvalidOperands),
+        }
+    }
+
+    /// Converts this expression into the condition of a conditional.
+    /// 
+    /// A matrix is true if it is not empty and all of its values are true.
+    pub fn to_condition(&self) -> Result<bool, Error> {
+        match self {
+            Expression::Matrix { values: v, .. } => {
+                let mut condition = !v.is_empty();
+                for value in v {
+                    condition &= value.to_bool()?;
+                }
+                Ok(condition)
+            },
+            _ => self.to_bool(),
+        }
+    }
+
+    /// Splits this expression into a value and its units.
+    /// 
+    /// Expressions other than quantities are dimensionless.
+    pub fn to_quantity(self) -> (Expression, Unit) {
+        match self {
+            Expression::Quantity {
+                value: v,
+                unit: u,
+            } => (*v, u),
+            _ => (self, Unit::none()),
+        }
+    }
+
+    /// Converts this expression into a "native" matrix.
+    /// 
+    /// Numbers are converted into 1x1 matrices.
+    pub fn to_matrix(&self) -> Result<Matrix, Error> {
+        match self {
+            Expression::Matrix {
+                rows: r,
+                cols: c,
+                values: v,
+            } => {
+                // Convert each value in the matrix from `Expression` to `f64`.
+                let mut values: Vec<f64> = Vec::new();
+                for value in v {
+                    if let Self::Int (i) = value {
+                        values.push(*i as f64);
+                    } else if let Self::Float (f) = value {
+                        values.push(*f);
+                    } else if let Self::Bool (b) = value {
+                        values.push(*b as i64 as f64);
+                    } else if let Self::Rational (q) = value {
+                        values.push(q.to_f64().unwrap_or(f64::NAN));
+                    } else if let Self::Complex (_) = value {
+                        return Err(ComplexNotSupported);
+                    } else if let Self::Quantity { .. } = value {
+                        return Err(UnitsNotSupported);
+                    } else if let Self::Uncertain (_) = value {
+                        return Err(UncertaintyNotSupported);
+                    } else {
+                        // A value in one of the matrices is not a numeric literal
+                        return Err(InvalidValue);
+                    }
+                }
+                Ok(Matrix::new(*r, *c, values))
+            },
+            Expression::Int (i) => Ok(Matrix::new(1, 1, vec![*i as f64])),
+            Expression::Float (f) => Ok(Matrix::new(1, 1, vec![*f])),
+            Expression::Bool (b) => Ok(Matrix::new(1, 1, vec![*b as i64 as f64])),
+            Expression::Rational (q) => Ok(Matrix::new(1, 1, vec![q.to_f64().unwrap_or(f64::NAN)])),
+            Expression::Complex (_) => Err(ComplexNotSupported),
+            Expression::Quantity { .. } => Err(UnitsNotSupported),
+            Expression::Uncertain (_) => Err(UncertaintyNotSupported),
+
+            // This is not a matrix or a number
+            _ => Err(InvalidOperands),
+        }
+    }
+
+    /// Converts this expression into a "native" complex matrix.
+    /// 
+    /// Real numbers are converted into complex numbers with no imaginary part.
+    pub fn to_complex_matrix(&self) -> Result<Matrix<Complex64>, Error> {
+        let (rows, cols, values) = match self {
+            Expression::Matrix {
+                rows: r,
+                cols: c,
+                values: v,
+            } => (*r, *c, v.as_slice()),
+            scalar if scalar.is_scalar() => (1, 1, std::slice::from_ref(scalar)),
+            Expression::Quantity { .. } => return Err(UnitsNotSupported),
+            _ => return Err(InvalidOperands),
+        };
+
+        let mut output = Vec::new();
+        for value in values {
+            match value {
+                Self::Complex (z) => output.push(*z),
+                Self::Uncertain (_) => return Err(UncertaintyNotSupported),
+                _ => output.push(Complex64::new(value.to_matrix().map_err(|_| InvalidValue)?[[0, 0]], 0.0)),
+            }
+        }
+
+        Ok(Matrix::new(rows, cols, output))
+    }
+
+    /// Converts this expression into a "native" matrix of uncertain values.
+    /// 
+    /// Numbers are converted into exact values, with no uncertainty.
+    pub fn to_uncertain_matrix(&self) -> Result<Matrix<Uncertain>, Error> {
+        let (rows, cols, values) = match self {
+            Expression::Matrix {
+                rows: r,
+                cols: c,
+                values: v,
+            } => (*r, *c, v.as_slice()),
+            scalar if scalar.is_scalar() => (1, 1, std::slice::from_ref(scalar)),
+            Expression::Quantity { .. } => return Err(UnitsNotSupported),
+            _ => return Err(InvalidOperands),
+        };
+
+        let mut output = Vec::new();
+        for value in values {
+            match value {
+                Self::Uncertain (u) => output.push(u.to_owned()),
+                Self::Complex (_) => return Err(ComplexNotSupported),
+                _ => output.push(Uncertain::exact(value.to_matrix().map_err(|_| InvalidValue)?[[0, 0]])),
+            }
+        }
+
+        Ok(Matrix::new(rows, cols, output))
+    }
+
+    /// Converts this exact expression into a "native" matrix of rational numbers.
+    pub fn to_rational_matrix(&self) -> Result<Matrix<BigRational>, Error> {
+        let (rows, cols, values) = match self {
+            Expression::Matrix {
+                rows: r,
+                cols: c,
+                values: v,
+            } => (*r, *c, v.as_slice()),
+            scalar if scalar.is_scalar() => (1, 1, std::slice::from_ref(scalar)),
+            Expression::Quantity { .. } => return Err(UnitsNotSupported),
+            _ => return Err(InvalidOperands),
+        };
+
+        let mut output = Vec::new();
+        for value in values {
+            output.push(rational_scalar(value)?);
+        }
+
+        Ok(Matrix::new(rows, cols, output))
+    }
+}
+
+/// Converts a "native" matrix back into an expression.
+impl From<Matrix> for Expression {
+    fn from(matrix: Matrix) -> Self {
+        let values = matrix.vals().iter().map(|x| Self::Float (*x)).collect::<Vec<Self>>();
+
+        Self::Matrix {
+            rows: matrix.rows(),
+            cols: matrix.cols(),
+            values,
+        }
+    }
+}
+
+
+/// Converts a "native" complex matrix back into an expression.
+impl From<Matrix<Complex64>> for Expression {
+    fn from(matrix: Matrix<Complex64>) -> Self {
+        let values = matrix.vals().iter().map(|z| Self::Complex (*z)).collect::<Vec<Self>>();
+
+        Self::Matrix {
+            rows: matrix.rows(),
+            cols: matrix.cols(),
+            values,
+        }
+    }
+}
+
+
+/// Converts a "native" matrix of rational numbers back into an expression.
+impl From<Matrix<BigRational>> for Expression {
+    fn from(matrix: Matrix<BigRational>) -> Self {
+        let values = matrix.vals().iter().map(|q| Self::Rational (q.to_owned())).collect::<Vec<Self>>();
+
+        Self::Matrix {
+            rows: matrix.rows(),
+            cols: matrix.cols(),
+            values,
+        }
+    }
+}
+
+
+/// Converts a "native" matrix of uncertain values back into an expression.
+impl From<Matrix<Uncertain>> for Expression {
+    fn from(matrix: Matrix<Uncertain>) -> Self {
+        let values = matrix.vals().iter().map(|u| Self::Uncertain (u.to_owned())).collect::<Vec<Self>>();
+
+        Self::Matrix {
+            rows: matrix.rows(),
+            cols: matrix.cols(),
+            values,
+        }
+    }
+}
+
+
+/// Tolerance (in steps) used to decide whether a range includes its end.
+const RANGE_TOLERANCE: f64 = 1e-10;
+
+/// Largest number of values in a range.
+const MAX_RANGE_LENGTH: usize = 1 << 24;
+
+
+/// Updates the variables of an outer scope with their values in an inner scope.
+/// 
+/// Variables that were only declared in the inner scope are discarded.
+fn update(outer: &mut HashMap<String, Expression>, mut inner: HashMap<String, Expression>) {
+    for (name, value) in outer.iter_mut() {
+        if let Some(v) = inner.remove(name) {
+            *value = v;
+        }
+    }
+}
+
+
+/// Gets the variables in scope that the body of a function or lambda uses,
+/// other than its parameters.
+fn capture(body: &Expression, params: &[String], variables: &HashMap<String, Expression>) -> HashMap<String, Expression> {
+    let mut used = HashSet::new();
+    names(body, &mut used);
+    for param in params {
+        used.remove(param);
+    }
+
+    used.into_iter()
+        .filter_map(|name| variables.get(&name).map(|value| (name, value.to_owned())))
+        .collect()
+}
+
+/// Collects the names of all variables and functions used in an expression.
+/// 
+/// Closures have already captured their own variables, so they are skipped.
+fn names(expression: &Expression, output: &mut HashSet<String>) {
+    match expression {
+        Expression::Identifier (s) => {
+            output.insert(s.to_owned());
+        },
+        Expression::Assignment {
+            identifier: i,
+            value: v,
+        } => {
+            output.insert(i.to_owned());
+            names(v, output);
+        },
+        Expression::IndexedAssignment {
+            identifier: i,
+            indices: n,
+            value: v,
+            ..
+        } => {
+            output.insert(i.to_owned());
+            n.iter().for_each(|e| names(e, output));
+            names(v, output);
+        },
+        Expression::MultipleAssignment {
+            identifiers: i,
+            value: v,
+            ..
+        } => {
+            output.extend(i.iter().cloned());
+            names(v, output);
+        },
+        Expression::Call {
+            name: n,
+            args: a,
+            ..
+        } => {
+            output.insert(n.to_owned());
+            a.iter().for_each(|e| names(e, output));
+        },
+        Expression::Index {
+            target: t,
+            indices: i,
+            ..
+        } => {
+            names(t, output);
+            i.iter().for_each(|e| names(e, output));
+        },
+        Expression::Range {
+            start: s,
+            step: t,
+            end: e,
+            ..
+        } => {
+            names(s, output);
+            if let Some(t) = t {
+                names(t, output);
+            }
+            names(e, output);
+        },
+        Expression::Conditional {
+            condition: c,
+            then: t,
+            otherwise: o,
+            ..
+        } => {
+            names(c, output);
+            names(t, output);
+            if let Some(o) = o {
+                names(o, output);
+            }
+        },
+        Expression::For {
+            variable: v,
+            iterable: i,
+            body: b,
+            ..
+        } => {
+            output.insert(v.to_owned());
+            names(i, output);
+            names(b, output);
+        },
+        Expression::While {
+            condition: c,
+            body: b,
+            ..
+        } => {
+            names(c, output);
+            names(b, output);
+        },
+        Expression::BinOp {
+            left: l,
+            right: r,
+            ..
+        } => {
+            names(l, output);
+            names(r, output);
+        },
+        Expression::UnaryOp { operand: v, .. }
+        | Expression::Quantity { value: v, .. }
+        | Expression::Function { body: v, .. }
+        | Expression::Lambda { body: v, .. } => names(v, output),
+        Expression::Matrix { values: v, .. }
+        | Expression::Block { statements: v } => v.iter().for_each(|e| names(e, output)),
+        _ => (),
+    }
+}
+
+/// Gets the number of rows and columns, and the values, of a matrix.
+/// 
+/// Numbers are treated as 1x1 matrices.
+fn dimensions(expression: Expression) -> Result<(usize, usize, Vec<Expression>), Error> {
+    match expression {
+        Expression::Matrix {
+            rows: r,
+            cols: c,
+            values: v,
+        } => Ok((r, c, v)),
+        scalar if scalar.is_scalar() => Ok((1, 1, vec![scalar])),
+        _ => Err(InvalidOperands),
+    }
+}
+
+
+/// Concatenates a matrix of matrices (and numbers) into a single matrix.
+/// 
+/// Values in the same row must have the same number of rows, and each row
+/// must have the same total number of columns.  Empty matrices are ignored.
+fn concatenate(rows: usize, cols: usize, values: Vec<Expression>) -> Result<Expression, Error> {
+    let mut blocks = Vec::new();
+    for value in values {
+        blocks.push(dimensions(value)?);
+    }
+
+    let mut output = Vec::new();
+    let mut total_rows = 0;
+    let mut total_cols = None;
+
+    for i in 0..rows {
+        let row = blocks[i*cols..(i + 1)*cols].iter()
+            .filter(|(r, c, _)| r*c != 0)
+            .collect::<Vec<_>>();
+
+        let height = match row.first() {
+            Some((r, _, _)) => *r,
+            None => continue,
+        };
+        if row.iter().any(|(r, _, _)| *r != height) {
+            return Err(ImproperDimensions);
+        }
+
+        let width = row.iter().map(|(_, c, _)| c).sum::<usize>();
+        if total_cols.is_some_and(|c| c != width) {
+            return Err(ImproperDimensions);
+        }
+        total_cols = Some (width);
+
+        for r in 0..height {
+            for (_, c, v) in &row {
+                output.extend_from_slice(&v[r*c..(r + 1)*c]);
+            }
+        }
+        total_rows += height;
+    }
+
+    match total_cols {
+        Some(c) => Ok(Expression::Matrix {
+            rows: total_rows,
+            cols: c,
+            values: output,
+        }),
+        None => Ok(Expression::Matrix {
+            rows: 1,
+            cols: 0,
+            values: output,
+        }),
+    }
+}
+
+
+/// Resolves a list of indices into a matrix with the given dimensions.
+/// 
+/// Returns the (row-major) positions of the selected values, along with the
+/// dimensions of the selection.
+fn select(rows: usize, cols: usize, indices: &[Expression], variables: &mut HashMap<String, Expression>) -> Result<(Vec<usize>, (usize, usize)), Error> {
+    match indices {
+        [i] => {
+            let positions = resolve_index(i, rows*cols, variables)?;
+            let count = positions.len();
+
+            // Column vectors stay column vectors
+            let shape = if cols == 1 && rows != 1 {
+                (count, 1)
+            } else {
+                (1, count)
+            };
+
+            Ok((positions, shape))
+        },
+        [i, j] => {
+            let selected_rows = resolve_index(i, rows, variables)?;
+            let selected_cols = resolve_index(j, cols, variables)?;
+
+            let mut positions = Vec::new();
+            for r in &selected_rows {
+                for c in &selected_cols {
+                    positions.push(r*cols + c);
+                }
+            }
+
+            Ok((positions, (selected_rows.len(), selected_cols.len())))
+        },
+        _ => Err(InvalidIndex),
+    }
+}
+
+
+/// Resolves a single index into a dimension with the given extent,
+/// returning zero-based positions.
+/// 
+/// Within the index, `end` refers to the extent of the dimension.  An index made
+/// of booleans is a mask, selecting the positions at which it is true.
+fn resolve_index(index: &Expression, extent: usize, variables: &mut HashMap<String, Expression>) -> Result<Vec<usize>, Error> {
+    let previous = variables.insert("end".to_string(), Expression::Int (extent as i64));
+    let simplified = index.simplify(variables);
+    match previous {
+        Some(p) => variables.insert("end".to_string(), p),
+        None => variables.remove("end"),
+    };
+
+    let values = match simplified? {
+        Expression::Matrix { values: v, .. } => v,
+        scalar => vec![scalar],
+    };
+
+    if !values.is_empty() && values.iter().all(|v| matches!(v, Expression::Bool (_))) {
+        if values.len() > extent {
+            return Err(IndexOutOfBounds);
+        }
+
+        let positions = values.iter().enumerate()
+            .filter(|(_, v)| **v == Expression::Bool (true))
+            .map(|(k, _)| k)
+            .collect();
+        return Ok(positions);
+    }
+
+    let mut positions = Vec::new();
+    for value in values {
+        let position = match value {
+            Expression::Int (i) if i >= 1 => i as usize,
+            _ => return Err(InvalidIndex),
+        };
+
+        if position > extent {
+            return Err(IndexOutOfBounds);
+        }
+
+        positions.push(position - 1);
+    }
+
+    Ok(positions)
+}
+
+
+/// Gets the precedence of a binary operator, for display purposes.
+fn precedence(op: &str) -> u8 {
+    match op {
+        "||" => 3,
+        "&&" => 4,
+        "==" | "!=" | "<" | ">" | "<=" | ">=" => 5,
+        "+" | "-" | "±" => 7,
+        "*" | "/" | "\\" | ".*" | "./" => 8,
+        "^" | ".^" => 9,
+        _ => 0,
+    }
+}
+
+
+/// Checks whether or not a binary operator is a (right-associative) power.
+fn is_power(op: &str) -> bool {
+    matches!(op, "^" | ".^")
+}
+
+
+/// Executes the given binary operation on two scalars.
+/// 
+/// Comparisons and logical operators produce booleans, while arithmetic treats
+/// booleans as `0` or `1`.  Arithmetic on integers and rational numbers is exact,
+/// except for roots, which are computed with floats.
+fn scalar_binop(left: Expression, o: &str, right: Expression) -> Result<Expression, Error> {
+    if let "&&" | "||" = o {
+        return Ok(Expression::Bool (match o {
+            "&&" => left.to_bool()? && right.to_bool()?,
+            _ => left.to_bool()? || right.to_bool()?,
+        }));
+    }
+
+    if o == "±" || left.is_uncertain() || right.is_uncertain() {
+        return uncertain_binop(left, o, right);
+    }
+
+    // Negative numbers have complex roots, so `(-4)^0.5` is `2i`
+    let is_complex_root = o.ends_with('^')
+        && scalar(&left).is_ok_and(|x| x < 0.0)
+        && scalar(&right).is_ok_and(|y| y.fract() != 0.0);
+
+    if left.is_complex() || right.is_complex() || is_complex_root {
+        let (x, y) = (complex_scalar(&left)?, complex_scalar(&right)?);
+
+        return match o {
+            "==" => Ok(Expression::Bool (x == y)),
+            "!=" => Ok(Expression::Bool (x != y)),
+
+            // Complex numbers are not ordered
+            "<" | ">" | "<=" | ">=" => Err(InvalidOperands),
+            _ => Expression::Complex (complex_binop(x, y, o)?).simplify(&mut HashMap::new()),
+        };
+    }
+
+    if left.is_exact() && right.is_exact() {
+        if let Some(result) = rational_binop(&rational_scalar(&left)?, &rational_scalar(&right)?, o)? {
+            return Ok(result);
+        }
+    }
+
+    let (x, y) = (scalar(&left)?, scalar(&right)?);
+
+    match o {
+        "==" => return Ok(Expression::Bool (x == y)),
+        "!=" => return Ok(Expression::Bool (x != y)),
+        "<" => return Ok(Expression::Bool (x < y)),
+        ">" => return Ok(Expression::Bool (x > y)),
+        "<=" => return Ok(Expression::Bool (x <= y)),
+        ">=" => return Ok(Expression::Bool (x >= y)),
+        _ => (),
+    }
+
+    let f = binop(x, y, o)?;
+    match (left, right) {
+        (Expression::Float (_), _) | (_, Expression::Float (_)) => Ok(Expression::Float (f)),
+
+        // Evaluate this as a float, then try to cast it to an `Int`
+        _ => if f.fract() == 0.0 && f.abs() < i64::MAX as f64 {
+            Ok(Expression::Int (f as i64))
+        } else {
+            Ok(Expression::Float (f))
+        },
+    }
+}
+
+
+/// Executes the given binary operation on two floats.
+/// 
+/// On scalars, element-wise operators behave like their ordinary counterparts.
+pub fn binop(x: f64, y: f64, binop: &str) -> Result<f64, Error> {
+    match binop {
+        "+" => Ok(x + y),
+        "-" => Ok(x - y),
+        "*" | ".*" => Ok(x * y),
+        "/" | "./" => {
+            if y == 0.0 {
+                Err(DividedByZero)
+            } else {
+                Ok(x / y)
+            }
+        },
+        "^" | ".^" => {
+            if x == 0.0 && y < 0.0 {
+                Err(DividedByZero)
+            } else {
+                Ok(x.powf(y))
+            }
+        },
+        _ => Err(InvalidOperator),
+    }
+}
+
+
+/// Multiplies a value by a scale factor, leaving it untouched if the factor is 1.
+pub fn scale(value: Expression, factor: f64) -> Result<Expression, Error> {
+    if factor == 1.0 {
+        return Ok(value);
+    }
+
+    Expression::BinOp {
+        left: Box::new(value),
+        op: "*".to_string(),
+        right: Box::new(Expression::Float (factor)),
+        span: Span::default(),
+    }.simplify(&mut HashMap::new())
+}
+
+
+/// Executes the given binary operation on two scalars, at least one of which is uncertain,
+/// propagating their uncertainty to first order.
+/// 
+/// The `±` operator makes a new uncertain value from two exact numbers.  Comparisons
+/// only compare nominal values.
+fn uncertain_binop(left: Expression, o: &str, right: Expression) -> Result<Expression, Error> {
+    let uncertain = |x: &Expression| match x {
+        Expression::Uncertain (u) => Ok(u.to_owned()),
+        _ => Ok(Uncertain::exact(scalar(x)?)),
+    };
+    let (x, y) = (uncertain(&left)?, uncertain(&right)?);
+
+    let result = match o {
+        "±" => {
+            if left.is_uncertain() || right.is_uncertain() {
+                return Err(InvalidOperands);
+            }
+            Uncertain::new(x.nominal(), y.nominal())
+        },
+        "+" => x + y,
+        "-" => x - y,
+        "*" | ".*" => x*y,
+        "/" | "./" => {
+            if y.nominal() == 0.0 {
+                return Err(DividedByZero);
+            }
+            x/y
+        },
+        "^" | ".^" => {
+            if x.nominal() == 0.0 && y.nominal() < 0.0 {
+                return Err(DividedByZero);
+            }
+            x.pow(&y)
+        },
+        "==" => return Ok(Expression::Bool (x.nominal() == y.nominal())),
+        "!=" => return Ok(Expression::Bool (x.nominal() != y.nominal())),
+        "<" => return Ok(Expression::Bool (x.nominal() < y.nominal())),
+        ">" => return Ok(Expression::Bool (x.nominal() > y.nominal())),
+        "<=" => return Ok(Expression::Bool (x.nominal() <= y.nominal())),
+        ">=" => return Ok(Expression::Bool (x.nominal() >= y.nominal())),
+        "&&" | "||" => return Err(InvalidOperands),
+        _ => return Err(InvalidOperator),
+    };
+
+    Expression::Uncertain (result).simplify(&mut HashMap::new())
+}
+
+
+/// Converts an exact number (an integer, a rational number or a boolean) into a rational number.
+fn rational_scalar(expression: &Expression) -> Result<BigRational, Error> {
+    match expression {
+        Expression::Int (i) => Ok(BigRational::from_integer(BigInt::from(*i))),
+        Expression::Bool (b) => Ok(BigRational::from_integer(BigInt::from(*b as i64))),
+        Expression::Rational (q) => Ok(q.to_owned()),
+        _ => Err(InvalidValue),
+    }
+}
+
+
+/// Largest number of bits in an exact power, beyond which powers are computed with floats.
+const MAX_EXACT_BITS: u64 = 1 << 16;
+
+
+/// Executes the given binary operation on two rational numbers, exactly.
+/// 
+/// Returns `None` if the result cannot be computed exactly (like the root
+/// in `2^(1/2)`), in which case it should be computed with floats.
+fn rational_binop(x: &BigRational, y: &BigRational, binop: &str) -> Result<Option<Expression>, Error> {
+    let result = match binop {
+        "+" => x + y,
+        "-" => x - y,
+        "*" | ".*" => x * y,
+        "/" | "./" => {
+            if y.is_zero() {
+                return Err(DividedByZero);
+            }
+            x / y
+        },
+        "^" | ".^" => {
+            let n = match (y.is_integer(), y.to_integer().to_i32()) {
+                (true, Some(n)) => n,
+                _ => return Ok(None),
+            };
+            if x.is_zero() && n < 0 {
+                return Err(DividedByZero);
+            }
+
+            let bits = x.numer().bits().max(x.denom().bits());
+            if bits.saturating_mul(n.unsigned_abs() as u64) > MAX_EXACT_BITS {
+                return Ok(None);
+            }
+            x.pow(n)
+        },
+        "==" => return Ok(Some(Expression::Bool (x == y))),
+        "!=" => return Ok(Some(Expression::Bool (x != y))),
+        "<" => return Ok(Some(Expression::Bool (x < y))),
+        ">" => return Ok(Some(Expression::Bool (x > y))),
+        "<=" => return Ok(Some(Expression::Bool (x <= y))),
+        ">=" => return Ok(Some(Expression::Bool (x >= y))),
+        _ => return Err(InvalidOperator),
+    };
+
+    Expression::Rational (result).simplify(&mut HashMap::new()).map(Some)
+}
+
+
+/// Converts a number (or unit matrix) into a complex number.
+fn complex_scalar(expression: &Expression) -> Result<Complex64, Error> {
+    let matrix = expression.to_complex_matrix()?;
+
+    if matrix.rows() != 1 || matrix.cols() != 1 {
+        return Err(RequiresUnitMatrix);
+    }
+
+    Ok(matrix[[0, 0]])
+}
+
+
+/// Executes the given binary operation on two complex numbers.
+/// 
+/// Integer powers are computed by repeated multiplication, so that `(1 + 1i)^2`
+/// is exactly `2i`.
+pub fn complex_binop(x: Complex64, y: Complex64, binop: &str) -> Result<Complex64, Error> {
+    let zero = Complex64::new(0.0, 0.0);
+
+    match binop {
+        "+" => Ok(x + y),
+        "-" => Ok(x - y),
+        "*" | ".*" => Ok(x * y),
+        "/" | "./" => {
+            if y == zero {
+                Err(DividedByZero)
+            } else {
+                Ok(x / y)
+            }
+        },
+        "^" | ".^" => {
+            if x == zero && y.re < 0.0 {
+                Err(DividedByZero)
+            } else if y.im == 0.0 && y.re.fract() == 0.0 && y.re.abs() <= i32::MAX as f64 {
+                Ok(x.powi(y.re as i32))
+            } else if x == zero {
+                Ok(zero)
+            } else {
+                Ok(x.powc(y))
+            }
+        },
+        _ => Err(InvalidOperator),
+    }
+}
+
+
+/// Formats one part of a complex number, omitting the decimals of whole numbers.
+fn format_part(f: f64) -> String {
+    if f.fract() == 0.0 && f.abs() < i64::MAX as f64 {
+        format!("{}", f as i64)
+    } else {
+        format!("{:.8}", f)
+    }
+}
+
+
+/// Raises a square matrix to an integer power by repeated squaring.
+/// 
+/// Negative powers are computed from the inverse of the matrix.
+/// `span` is the location of the operation in the source.
+pub fn matrix_power(values: Vec<Expression>, dim: usize, n: i64, span: Span) -> Result<Expression, Error> {
+    let mut base = if n < 0 {
+        let matrix = Expression::Matrix {
+            rows: dim,
+            cols: dim,
+            values,
+        };
+        let inverse = if matrix.is_uncertain() {
+            Expression::from(Invert::evalpure(&matrix.to_uncertain_matrix()?)?)
+        } else if matrix.is_exact() {
+            Expression::from(Invert::evalpure(&matrix.to_rational_matrix()?)?)
+        } else if matrix.is_complex() {
+            Expression::from(Invert::evalpure(&matrix.to_complex_matrix()?)?)
+        } else {
+            Expression::from(Invert::evalpure(&matrix.to_matrix()?)?)
+        };
+
+        match inverse {
+            Expression::Matrix { values: v, .. } => v,
+            _ => unreachable!(),
+        }
+    } else {
+        values
+    };
+
+    // Start with the identity matrix
+    let mut result = Vec::new();
+    for i in 0..dim {
+        for j in 0..dim {
+            result.push(Expression::Int ((i == j) as i64));
+        }
+    }
+
+    let mut exponent = n.unsigned_abs();
+    while exponent > 0 {
+        if exponent % 2 == 1 {
+            result = match matrix_dot(result, base.to_owned(), dim, dim, dim, span)? {
+                Expression::Matrix { values: v, .. } => v,
+                _ => unreachable!(),
+            };
+        }
+
+        exponent /= 2;
+        if exponent > 0 {
+            base = match matrix_dot(base.to_owned(), base, dim, dim, dim, span)? {
+                Expression::Matrix { values: v, .. } => v,
+                _ => unreachable!(),
+            };
+        }
+    }
+
+    Expression::Matrix {
+        rows: dim,
+        cols: dim,
+        values: result,
+    }.simplify(&mut HashMap::new())
+}
+
+
+/// Applies the given binary operation to each pair of corresponding values
+/// in two matrices of the same dimensions.
+/// 
+/// `span` is the location of the operation in the source.
+pub fn elementwise(left: Vec<Expression>, right: Vec<Expression>, rows: usize, cols: usize, op: &str, span: Span) -> Result<Expression, Error> {
+    let mut values = Vec::new();
+    for (l, r) in left.into_iter().zip(right) {
+        values.push(Expression::BinOp {
+            left: Box::new(l),
+            op: op.to_string(),
+            right: Box::new(r),
+            span,
+        }.simplify(&mut HashMap::new())?);
+    }
+
+    Ok(Expression::Matrix {
+        rows,
+        cols,
+        values,
+    })
+}
+
+
+/// Computes the dot product of two matrices.
+/// 
+/// `span` is the location of the operation in the source.
+pub fn matrix_dot(left: Vec<Expression>, right: Vec<Expression>, rows: usize, cols: usize, count: usize, span: Span) -> Result<Expression, Error> {
+    let mut values = Vec::new();
+    for i in 0..rows {
+        for j in 0..cols {
+            let mut cell = Expression::Int (0);
+            for k in 0..count {
+                // Add the addend to the cell
+                let addend = Expression::BinOp {
+                    left: Box::new(left[i*count + k].to_owned()),
+                    right: Box::new(right[k*cols + j].to_owned()),
+                    op: "*".to_string(),
+                    span,
+                };
+
+                cell = Expression::BinOp {
+                    left: Box::new(cell),
+                    right: Box::new(addend),
+                    op: "+".to_string(),
+                    span,
+                };
+            }
+            // Push the cell to the list of values
+            values.push(cell.simplify(&mut HashMap::new())?);
+        }
+    }
+
+    Ok(Expression::Matrix {
+        rows,
+        cols,
+        values,
+    })
+}
@@ -8,19 +8,29 @@ mod parser;
 mod standard;
 mod expression;
 mod matrix;
+mod units;
+mod uncertain;
 
 pub use tokenizer::{
+    Span,
     Token,
     TokenClass,
     Tokenizer,
 };
 
-pub use parser::Parser;
+pub use parser::{
+    Parser,
+    Statement,
+};
 pub use expression::Expression;
 pub use matrix::Matrix;
+pub use units::Unit;
+pub use uncertain::Uncertain;
 
 pub use standard::get_std_variables;
 
+use error::*;
+
 /// Allows parselet files to easily access necessary
 /// abstractions without long `use` statements.
 /// 
@@ -32,6 +42,7 @@ pub mod parselet_utils {
         PrefixParselet,
     };
     pub use crate::tokenizer::{
+        Span,
         Token,
         Tokenizer,
         TokenClass,
@@ -42,28 +53,870 @@ pub mod parselet_utils {
 
 /// Interprets a `String` of code into an `Expression`.
 /// 
-/// Also returns a boolean value indicating whether or not the output should be displayed.
-pub fn interpret(variables: &mut HashMap<String, Expression>, code: String) -> (Expression, bool) {
-    // Create a token stream from the code input.
-    let mut tokenizer = Tokenizer::from(code);
-    let is_silent = tokenizer.chk_silent();
+/// Every statement in the code is evaluated, and the value of the last one is returned,
+/// along with a boolean value indicating whether or not it should be displayed.  Use
+/// `run` to see the value of every statement.
+/// 
+/// Evaluation stops at the first error, which is returned to the caller.
+/// Expressions without a value (such as loops) are never displayed.
+pub fn interpret(variables: &mut HashMap<String, Expression>, code: String) -> Result<(Expression, bool), Error> {
+    // Blank lines (and lines containing only comments) evaluate to nothing.
+    let mut output = (Expression::Nil, true);
 
-    // Create a parser and parse from the tokenizer.
+    run(variables, code, |statement, expression| {
+        let is_silent = statement.is_silent() || *expression == Expression::Nil;
+        output = (expression.to_owned(), is_silent);
+    })?;
+
+    Ok(output)
+}
+
+
+/// Runs a program, given as a `String` of code made up of statements separated
+/// by semicolons or newlines.
+/// 
+/// After each statement is evaluated, `display` is called with the statement and its
+/// value, allowing the host to show the value if the statement is not silent.
+/// 
+/// Execution stops at the first error, which is returned to the caller.
+pub fn run<F>(variables: &mut HashMap<String, Expression>, code: String, mut display: F) -> Result<(), Error>
+where
+    F: FnMut(&Statement, &Expression),
+{
+    let mut tokenizer = Tokenizer::from(code)?;
     let parser = Parser::new();
-    let expression = parser.parse(&mut tokenizer, 0);
 
-    (expression.simplify(variables), is_silent)
+    while let Some(statement) = parser.parse_statement(&mut tokenizer)? {
+        let result = statement.get_expression().simplify(variables)?;
+        display(&statement, &result);
+    }
+
+    Ok(())
+}
+
+
+/// Checks whether or not a `String` of code is a complete statement.
+/// 
+/// A statement is incomplete if it opens a parenthesis, bracket or brace that it
+/// does not close, in which case the statement continues on the next line.  Code
+/// that cannot be tokenized is considered complete, so that its error can be reported.
+pub fn is_complete(code: &str) -> bool {
+    match Tokenizer::from(code.to_string()) {
+        Ok(t) => t.is_complete(),
+        Err(_) => true,
+    }
 }
 
 
 #[test]
 fn interpret_00() {
     let code = "3.1415".to_string();
-    println!("{}", interpret(code));
+    println!("{}", interpret(&mut get_std_variables(), code).unwrap().0);
 }
 
 #[test]
 fn interpret_01() {
     let code = "x = 4".to_string();
-    println!("{}", interpret(code));
-}
\ No newline at end of file
+    println!("{}", interpret(&mut get_std_variables(), code).unwrap().0);
+}
+
+#[test]
+fn interpret_02() {
+    let mut variables = get_std_variables();
+    let code = "[1 2; 3 4] * [1 2 3]".to_string();
+    let error = interpret(&mut variables, code).unwrap_err();
+    assert_eq!(error.kind(), &ImproperDimensions);
+    assert_eq!(error.span(), Some (Span::new((1, 12), (1, 13))));
+
+    let code = "y + 1".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &UndeclaredVariable ("y".to_string()));
+
+    let code = "foo(1)".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &CouldNotFindFunction);
+
+    // Errors in calls point at the whole call, starting with the function's name
+    let code = "1 + det([1 2])".to_string();
+    let error = interpret(&mut variables, code).unwrap_err();
+    assert_eq!(error.kind(), &SquareMatrixRequired);
+    assert_eq!(error.span(), Some (Span::new((1, 5), (1, 15))));
+}
+
+#[test]
+fn interpret_03() {
+    let mut variables = get_std_variables();
+    let _ = interpret(&mut variables, "x = 10".to_string()).unwrap();
+    let _ = interpret(&mut variables, "f(x, y) = x*y + sin(x)".to_string()).unwrap();
+    let _ = interpret(&mut variables, "g(x) = f(x, 2) / 2".to_string()).unwrap();
+
+    let (result, _) = interpret(&mut variables, "g(0)".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (0));
+
+    // Parameters do not clobber global variables
+    let (result, _) = interpret(&mut variables, "x".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (10));
+
+    let code = "f(1)".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &WrongNumberOfArgs);
+
+    // Functions see the variables where they are defined, not where they are called
+    let _ = interpret(&mut variables, "h(x) = x + w".to_string()).unwrap();
+    let code = "{ w = 5; h(1) }".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &UndeclaredVariable ("w".to_string()));
+
+    // ... but they can call themselves
+    let _ = interpret(&mut variables, "n(x) = if x > 0 { x * n(x - 1) } else { 1 }".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "n(5)".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (120));
+}
+
+#[test]
+fn interpret_04() {
+    let mut variables = get_std_variables();
+    let _ = interpret(&mut variables, "a = 2".to_string()).unwrap();
+    let _ = interpret(&mut variables, "f = x -> a*x + 1".to_string()).unwrap();
+
+    // Closures capture their environment when they are created
+    let _ = interpret(&mut variables, "a = 100".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "map(f, [1 2 3])".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[3 5 7]".to_string()).unwrap().0);
+
+    let code = "map((x, y) -> x, 1)".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &WrongNumberOfArgs);
+
+    let (result, _) = interpret(&mut variables, "integrate(x -> 3*x*x, 0, 2)".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (8));
+
+    let (result, _) = interpret(&mut variables, "root(x -> x*x - 2, 0, 2) - sqrt(2)".to_string()).unwrap();
+    assert!(matches!(result, Expression::Int (0)) || matches!(result, Expression::Float (f) if f.abs() < 1e-10));
+
+    let (result, _) = interpret(&mut variables, "root(x -> x*x - 2, 1) - sqrt(2)".to_string()).unwrap();
+    assert!(matches!(result, Expression::Int (0)) || matches!(result, Expression::Float (f) if f.abs() < 1e-10));
+
+    // Closures capture the variables used by the functions they call
+    let _ = interpret(&mut variables, "h(x) = x + a".to_string()).unwrap();
+    let _ = interpret(&mut variables, "k = x -> h(x)".to_string()).unwrap();
+    let _ = interpret(&mut variables, "a = 1".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "k(1)".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (101));
+
+    // Closures only capture what they use, so defining many does not copy them all
+    let _ = interpret(&mut variables, "f0 = x -> x".to_string()).unwrap();
+    for i in 1..=50 {
+        let _ = interpret(&mut variables, format!("f{} = x -> f{}(x) + 1", i, i - 1)).unwrap();
+    }
+    let (result, _) = interpret(&mut variables, "f50(0)".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (50));
+}
+
+#[test]
+fn interpret_05() {
+    let mut variables = get_std_variables();
+    let (result, _) = interpret(&mut variables, "2^3^2".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (512));
+
+    let (result, _) = interpret(&mut variables, "2*3^2 + (1 + 1)^3".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (26));
+
+    let (result, _) = interpret(&mut variables, "[1 1; 1 0]^10".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[89 55; 55 34]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "[2 0; 0 4]^-1".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[1/2 0; 0 1/4]".to_string()).unwrap().0);
+
+    let code = "[1 2 3]^2".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &SquareMatrixRequired);
+}
+
+#[test]
+fn interpret_06() {
+    let mut variables = get_std_variables();
+    let _ = interpret(&mut variables, "x = 3".to_string()).unwrap();
+
+    let (result, _) = interpret(&mut variables, "-x^2".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (-9));
+
+    let (result, _) = interpret(&mut variables, "-(x + 1) + +x - -x".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (2));
+
+    let (result, _) = interpret(&mut variables, "x-1".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (2));
+
+    let (result, _) = interpret(&mut variables, "-sin(0) + 2^-1".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "1/2".to_string()).unwrap().0);
+
+    // Inside a matrix, a sign attached to a value starts a new value
+    let (result, _) = interpret(&mut variables, "-[1 -2 x - 1 x-1 (x -1)]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[-1 2 -2 -2 -2]".to_string()).unwrap().0);
+}
+
+#[test]
+fn interpret_07() {
+    let mut variables = get_std_variables();
+    let _ = interpret(&mut variables, "A = [1 2; 3 4]".to_string()).unwrap();
+
+    let (result, _) = interpret(&mut variables, "A .* A - A ./ [1 2; 3 4] + A.^2".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[1 7; 17 31]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "2 - A / 2".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[3/2 1; 1/2 0]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "12 ./ A + 0.5".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[12.5 6.5; 4.5 3.5]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "A / A".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[1 0; 0 1]".to_string()).unwrap().0);
+
+    let code = "A + [1 2 3]".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &ImproperDimensions);
+
+    let code = "[1 2] .* [1 2 3]".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &ImproperDimensions);
+}
+
+#[test]
+fn interpret_08() {
+    let mut variables = get_std_variables();
+    let _ = interpret(&mut variables, "A = [1 2 3; 4 5 6; 7 8 9]".to_string()).unwrap();
+
+    let (result, _) = interpret(&mut variables, "A[2, 3]".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (6));
+
+    let (result, _) = interpret(&mut variables, "A[2, :]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[4 5 6]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "A[:, end]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[3; 6; 9]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "A[1:end-1, 2]'".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[2 5]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "[A[1, 1] A[end]]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[1 9]".to_string()).unwrap().0);
+
+    let _ = interpret(&mut variables, "A[2, 3] = 7".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "A[3, 2:3] = [0 -1]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[1 2 3; 4 5 7; 7 0 -1]".to_string()).unwrap().0);
+
+    let code = "A[4, 1]".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &IndexOutOfBounds);
+
+    let code = "A[0]".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &InvalidIndex);
+}
+
+#[test]
+fn interpret_09() {
+    let mut variables = get_std_variables();
+
+    let (result, _) = interpret(&mut variables, "1:4".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[1 2 3 4]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "0:0.1:0.3".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[0 0.1 0.2 0.30000000000000004]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "[5:-2:1 2-1:2]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[5 3 1 1 2]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "(0:0.1:1)[end]".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (1));
+
+    let _ = interpret(&mut variables, "A = [1 2 3; 4 5 6]".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "A[2, 3:-1:1]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[6 5 4]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "[A[:, 1] [7; 8]; 0:1]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[1 7; 4 8; 0 1]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "A[1, 3:2]".to_string()).unwrap();
+    assert_eq!(result, Expression::Matrix { rows: 1, cols: 0, values: Vec::new() });
+
+    let code = "1:0:5".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &ZeroStep);
+
+    let code = "1:1e12".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &RangeTooLarge);
+}
+
+#[test]
+fn interpret_10() {
+    let mut variables = get_std_variables();
+    let _ = interpret(&mut variables, "A = [1 2 3; 4 5 6]".to_string()).unwrap();
+
+    let (result, _) = interpret(&mut variables, "1 + 1 == 2 && !(3 <= 2) || x".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    // The right-hand side is never evaluated, so `x` need not exist
+    let (result, _) = interpret(&mut variables, "1 > 2 && x".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (false));
+
+    let (result, _) = interpret(&mut variables, "A >= 3 != [true false true; false true false]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[true false false; true false true]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "A[A > 2 && A < 6]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[3 4 5]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "[any(A > 5) all(A > 5) all(A != 0)]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[true false true]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "(A == 2) + 1".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[1 2 1; 1 1 1]".to_string()).unwrap().0);
+}
+
+#[test]
+fn interpret_11() {
+    let mut variables = get_std_variables();
+    let _ = interpret(&mut variables, "sat(x) = if x > 1 { 1 } else if x < -1 { -1 } else { x }".to_string()).unwrap();
+
+    let (result, _) = interpret(&mut variables, "[sat(3) sat(-2) sat(0.5)]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[1 -1 0.5]".to_string()).unwrap().0);
+
+    // The branch that is not taken is never evaluated
+    let (result, _) = interpret(&mut variables, "if [1 2] > 0 { 1 } else { y }".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (1));
+
+    let (result, _) = interpret(&mut variables, "if false { y }".to_string()).unwrap();
+    assert_eq!(result, Expression::Nil);
+
+    // Variables assigned in a branch belong to the enclosing scope
+    let (result, _) = interpret(&mut variables, "c = false; if c { y = 1 } else { y = 2 }; y".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (2));
+
+    let (result, _) = interpret(&mut variables, "if y > 1 { z = y*10 }; z".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (20));
+
+    let _ = interpret(&mut variables, "A = [1 -2; -3 4]".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "where(A > 0, A, 0)".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[1 0; 0 4]".to_string()).unwrap().0);
+
+    let code = "where([true false], 1, [1 2 3])".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &ImproperDimensions);
+}
+
+#[test]
+fn interpret_12() {
+    let mut variables = get_std_variables();
+    let _ = interpret(&mut variables, "s = 0".to_string()).unwrap();
+
+    let code = "for i in 1:10 {\n    t = i^2\n    s = s + t\n}".to_string();
+    assert!(is_complete(&code));
+    assert!(!is_complete("for i in 1:10 {\n    s = s + i\n"));
+    let (result, is_silent) = interpret(&mut variables, code).unwrap();
+    assert_eq!(result, Expression::Nil);
+    assert!(is_silent);
+
+    // Only variables that already existed are updated by the loop
+    assert_eq!(variables.get("s"), Some (&Expression::Int (385)));
+    assert_eq!(variables.get("t"), None);
+    assert_eq!(variables.get("i"), None);
+
+    let _ = interpret(&mut variables, "n = 100".to_string()).unwrap();
+    let _ = interpret(&mut variables, "k = 0".to_string()).unwrap();
+    let _ = interpret(&mut variables, "while n > 1 { n = n/2; k = k + 1 }".to_string()).unwrap();
+    assert_eq!(variables.get("k"), Some (&Expression::Int (7)));
+
+    let _ = interpret(&mut variables, "c = [0 0]".to_string()).unwrap();
+    let _ = interpret(&mut variables, "for col in [1 2; 3 4] { c = c + col' }".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "c".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[3 7]".to_string()).unwrap().0);
+
+    // Statements in a block are separated like statements in a program
+    let code = "x = { 1 2 }".to_string();
+    let error = interpret(&mut variables, code).unwrap_err();
+    assert_eq!(error.kind(), &UnexpectedEof);
+    assert_eq!(error.span(), Some (Span::new((1, 9), (1, 10))));
+}
+
+#[test]
+fn interpret_13() {
+    let mut variables = get_std_variables();
+    let code = "
+        // Matrices and calls may span several lines
+        A = [1 2
+             3 4];
+        b = linspace(0,
+                     1, 2) // trailing comment
+        f(x) = {
+            y = x + 1
+            y^2
+        }
+        c = 0;
+        if false {
+            c = 1
+        }
+        else {
+            c = f(A[2, 2])
+        }
+    ".to_string();
+
+    let mut displayed = Vec::new();
+    run(&mut variables, code.to_owned(), |statement, expression| {
+        if !statement.is_silent() {
+            displayed.push((statement.get_span().excerpt(&code), expression.to_owned()));
+        }
+    }).unwrap();
+
+    assert_eq!(displayed.len(), 3);
+    assert_eq!(displayed[0].0, "b = linspace(0,\n                     1, 2)");
+    assert_eq!(displayed[2].1, Expression::Int (25));
+    assert_eq!(variables.get("c"), Some (&Expression::Int (25)));
+
+    let (result, _) = interpret(&mut variables, "A".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[1 2; 3 4]".to_string()).unwrap().0);
+
+    // Errors are located within the whole program
+    let error = run(&mut variables, "x = 1\n\ny = x +".to_string(), |_, _| ()).unwrap_err();
+    assert_eq!(error.span().map(|s| s.line()), Some (3));
+}
+
+#[test]
+fn interpret_14() {
+    let mut variables = get_std_variables();
+
+    let (result, is_silent) = interpret(&mut variables, "a = 1; b = 2; a + b".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (3));
+    assert!(!is_silent);
+
+    // Semicolons inside a matrix separate rows rather than statements
+    let (_, is_silent) = interpret(&mut variables, "c = [a; b]; d = [a b;]".to_string()).unwrap();
+    assert!(!is_silent);
+
+    let code = "c = [a; b]; d = [a b;]; a".to_string();
+    let mut displayed = Vec::new();
+    run(&mut variables, code.to_owned(), |statement, expression| {
+        if !statement.is_silent() {
+            displayed.push((statement.get_span().excerpt(&code), expression.to_owned()));
+        }
+    }).unwrap();
+    assert_eq!(displayed, vec![("a".to_string(), Expression::Int (1))]);
+
+    let (result, _) = interpret(&mut variables, "c' == d".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[true true]".to_string()).unwrap().0);
+
+    // Statements must be separated
+    let code = "a b".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().span(), Some (Span::new((1, 3), (1, 4))));
+}
+
+#[test]
+fn interpret_15() {
+    let mut variables = get_std_variables();
+    let _ = interpret(&mut variables, "θ = 30*deg".to_string()).unwrap();
+
+    let (result, _) = interpret(&mut variables, "2×sin(θ) · (τ/π) − e^0".to_string()).unwrap();
+    assert!(matches!(result, Expression::Float (f) if (f - 1.0).abs() < 1e-12));
+
+    let (result, _) = interpret(&mut variables, "1k + 0x10 + 0b11 + 2e3 + 1_000".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (4019));
+}
+
+#[test]
+fn interpret_16() {
+    use num_complex::Complex64;
+
+    let mut variables = get_std_variables();
+    let z = |re, im| Expression::Complex (Complex64::new(re, im));
+
+    let (result, _) = interpret(&mut variables, "3 + 4i".to_string()).unwrap();
+    assert_eq!(result, z(3.0, 4.0));
+    assert_eq!(result.to_string(), "3 + 4i");
+
+    let (result, _) = interpret(&mut variables, "(3 + 4j)*conj(3 + 4i)".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (25));
+
+    let (result, _) = interpret(&mut variables, "sqrt(-4)".to_string()).unwrap();
+    assert_eq!(result, z(0.0, 2.0));
+
+    let (result, _) = interpret(&mut variables, "[abs(3 - 4i) re(3 - 4i) im(3 - 4i)]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[5 3 -4]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "det([1 2i; 3 4])".to_string()).unwrap();
+    assert_eq!(result, z(4.0, -6.0));
+
+    let (result, _) = interpret(&mut variables, "inv([2i 0; 0 2]) == [-0.5i 0; 0 0.5]".to_string()).unwrap();
+    assert!(result.to_condition().unwrap());
+
+    let (result, _) = interpret(&mut variables, "[1 2i] * [1; 1i] == -1".to_string()).unwrap();
+    assert!(result.to_condition().unwrap());
+
+    let (result, _) = interpret(&mut variables, "sin(1i)".to_string()).unwrap();
+    assert!(matches!(result, Expression::Complex (w) if w.re == 0.0 && (w.im - 1f64.sinh()).abs() < 1e-12));
+
+    let (result, _) = interpret(&mut variables, "polar(2i) == [2 pi/2]".to_string()).unwrap();
+    assert!(result.to_condition().unwrap());
+
+    // Complex numbers are not ordered
+    let code = "1i < 2".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &InvalidOperands);
+}
+
+#[test]
+fn interpret_17() {
+    let mut variables = get_std_variables();
+
+    let (result, _) = interpret(&mut variables, "5 [kN]".to_string()).unwrap();
+    assert_eq!(result.to_string(), "5 kN");
+
+    let (result, _) = interpret(&mut variables, "3 m * 2 s^-1".to_string()).unwrap();
+    assert_eq!(result.to_string(), "6 m/s");
+
+    let (result, _) = interpret(&mut variables, "F = 5 [kg] * 2 [m/s^2]".to_string()).unwrap();
+    assert_eq!(result.to_string(), "10 N");
+
+    let (result, _) = interpret(&mut variables, "to(F, kN) == 0.01 [kN]".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    let (result, _) = interpret(&mut variables, "to(1 [ft], inch)".to_string()).unwrap();
+    assert!(matches!(result, Expression::Quantity { value, .. } if matches!(*value, Expression::Float (f) if (f - 12.0).abs() < 1e-9)));
+
+    // Units with the same dimension are converted, and dimensionless quantities are numbers
+    let (result, _) = interpret(&mut variables, "(1 km + 500 m)/(500 m)".to_string()).unwrap();
+    assert!(matches!(result, Expression::Float (f) if (f - 3.0).abs() < 1e-12));
+
+    let (result, _) = interpret(&mut variables, "sqrt(16 [m^2])".to_string()).unwrap();
+    assert_eq!(result.to_string(), "4 m");
+
+    let code = "1 m + 1 s".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &IncompatibleUnits);
+
+    let code = "to(F, m)".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &IncompatibleUnits);
+
+    let code = "2 [furlong]".to_string();
+    assert!(interpret(&mut variables, code).is_err());
+
+    // Units are not SI suffixes, so spacing does not matter
+    let (result, _) = interpret(&mut variables, "5m == 5 m && 1T == 1 [T]".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+    let (result, _) = interpret(&mut variables, "5m".to_string()).unwrap();
+    assert_eq!(result.to_string(), "5 m");
+
+    // Any parenthesized value can be annotated, but units are not variables
+    let _ = interpret(&mut variables, "x = 2 ± 0.1; y = 3".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "(x + 1) [m]".to_string()).unwrap();
+    assert_eq!(result.to_string(), "3.0 ± 0.1 m");
+
+    let (result, _) = interpret(&mut variables, "(y) [m] + sqrt(4) [m] == 5 m".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    // A bracket after an identifier is an index, even after a space
+    let (result, _) = interpret(&mut variables, "A = [1 2 3]; A [2]".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (2));
+
+    let (result, _) = interpret(&mut variables, "to(36 [km/h], m/s)".to_string()).unwrap();
+    assert_eq!(result.to_string(), "10 m/s");
+
+    let code = "m + 1".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &UndeclaredVariable ("m".to_string()));
+
+    let code = "sin(30 [deg])".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &UnknownUnit ("deg".to_string()));
+
+    // Variables are not units, even as the target of a conversion
+    let mut variables = get_std_variables();
+    let _ = interpret(&mut variables, "m = 2".to_string()).unwrap();
+    let code = "to(1 [km], m)".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &IncompatibleUnits);
+
+    let _ = interpret(&mut variables, "to(x, y) = x * y".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "to(3, m)".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (6));
+}
+
+#[test]
+fn interpret_18() {
+    let mut variables = get_std_variables();
+
+    let (result, _) = interpret(&mut variables, "g = 9.81 ± 0.02".to_string()).unwrap();
+    assert_eq!(result.to_string(), "9.81 ± 0.02");
+
+    let (result, _) = interpret(&mut variables, "x = 2 +- 0.03; x*x".to_string()).unwrap();
+    assert!(matches!(result, Expression::Uncertain (u) if (u.nominal() - 4.0).abs() < 1e-12 && (u.deviation() - 0.12).abs() < 1e-12));
+
+    // `+-` needs whitespace on both sides, so that adding a negative number is unchanged
+    let (result, _) = interpret(&mut variables, "a = 3; b = 2; [a+-b a + -b 1+-2]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[1 1 -1]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "a +- b".to_string()).unwrap();
+    assert_eq!(result.to_string(), "3 ± 2");
+
+    // Repeated variables are correlated
+    let (result, _) = interpret(&mut variables, "g - g".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (0));
+
+    let (result, _) = interpret(&mut variables, "(1 ± 0.03) + (1 ± 0.04)".to_string()).unwrap();
+    assert!(matches!(result, Expression::Uncertain (u) if (u.deviation() - 0.05).abs() < 1e-12));
+
+    let (result, _) = interpret(&mut variables, "sqrt(x)".to_string()).unwrap();
+    assert!(matches!(result, Expression::Uncertain (u) if (u.deviation() - 0.015/2f64.sqrt()).abs() < 1e-12));
+
+    // To first order, `cos` has no uncertainty at its peak
+    let (result, _) = interpret(&mut variables, "cos(0 ± 0.1)".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (1));
+
+    let (result, _) = interpret(&mut variables, "det([x 0; 0 x])".to_string()).unwrap();
+    assert!(matches!(result, Expression::Uncertain (u) if (u.deviation() - 0.12).abs() < 1e-12));
+
+    let (result, _) = interpret(&mut variables, "3.14159 ± 0.0042".to_string()).unwrap();
+    assert_eq!(result.to_string(), "3.142 ± 0.004");
+
+    let code = "integrate(t -> t, 0, x)".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &UncertaintyNotSupported);
+}
+
+#[test]
+fn interpret_19() {
+    let mut variables = get_std_variables();
+
+    let (result, _) = interpret(&mut variables, "1/3 + 1/6".to_string()).unwrap();
+    assert_eq!(result.to_string(), "1/2");
+
+    let (result, _) = interpret(&mut variables, "1/3*3 == 1".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    // Integers are not limited to 64 bits
+    let (result, _) = interpret(&mut variables, "2^100 + 1 - 2^100".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (1));
+
+    // ...including integer literals
+    let (result, _) = interpret(&mut variables, "99999999999999999999 + 1".to_string()).unwrap();
+    assert_eq!(result.to_string(), "100000000000000000000");
+
+    let (result, _) = interpret(&mut variables, "0xFFFFFFFFFFFFFFFFF - 2^68 + 0b10000000000000000000000000000000000000000000000000000000000000000".to_string()).unwrap();
+    assert_eq!(result.to_string(), "18446744073709551615");
+
+    let (result, _) = interpret(&mut variables, "-9223372036854775808".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (i64::MIN));
+
+    let (result, _) = interpret(&mut variables, "det([1 2; 3 4])".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (-2));
+
+    let (result, _) = interpret(&mut variables, "inv([1 2; 3 4])".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[-2 1; 3/2 -1/2]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "t([1/3 2/3])".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[1/3; 2/3]".to_string()).unwrap().0);
+
+    // Floats and transcendental functions are not exact
+    let (result, _) = interpret(&mut variables, "1/4 + 0.5".to_string()).unwrap();
+    assert_eq!(result, Expression::Float (0.75));
+
+    let (result, _) = interpret(&mut variables, "sin(1/2)".to_string()).unwrap();
+    assert_eq!(result, Expression::Float (0.5f64.sin()));
+
+    let (result, _) = interpret(&mut variables, "4^(1/2)".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (2));
+
+    let code = "inv([1 2; 2 4])".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &SingularMatrix);
+}
+
+#[test]
+fn interpret_20() {
+    let mut variables = get_std_variables();
+    let _ = interpret(&mut variables, "A = [1 2 0; 2 0.5 1; 4 1 3]".to_string()).unwrap();
+
+    // `lu(A)` is `L`, `U` and `P` side by side, with `PA = LU`
+    let _ = interpret(&mut variables, "F = lu(A)".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "all(abs(F[1:3, 7:9] * A - F[1:3, 1:3] * F[1:3, 4:6]) < 1e-12)".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    // Pivoting chooses the largest value in each column
+    let (result, _) = interpret(&mut variables, "F[1:3, 7:9]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[0 0 1; 1 0 0; 0 1 0]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "det(A)".to_string()).unwrap();
+    assert!(matches!(result, Expression::Float (f) if (f + 3.5).abs() < 1e-12));
+
+    let (result, _) = interpret(&mut variables, "all(abs(A * inv(A) - I(3)) < 1e-12)".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    // Large matrices are factorized in polynomial time
+    let (result, _) = interpret(&mut variables, "det(0.5*I(40))".to_string()).unwrap();
+    assert_eq!(result, Expression::Float (0.5f64.powi(40)));
+
+    let (result, _) = interpret(&mut variables, "det([0.1 0.2; 0.3 0.6])".to_string()).unwrap();
+    assert!(matches!(result, Expression::Int (0)) || matches!(result, Expression::Float (f) if f.abs() < 1e-15));
+
+    let code = "inv([0.1 0.2; 0.3 0.6])".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &SingularMatrix);
+
+    let code = "[1 2; 2 4]^-1".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &SingularMatrix);
+}
+
+#[test]
+fn interpret_21() {
+    let mut variables = get_std_variables();
+
+    // Exact systems have exact solutions
+    let (result, _) = interpret(&mut variables, "[1 2; 3 4] \\ [5; 6]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[-4; 9/2]".to_string()).unwrap().0);
+
+    // Symmetric positive definite systems, with several right-hand sides
+    let _ = interpret(&mut variables, "A = [4 1 0.5; 1 3 0; 0.5 0 2]".to_string()).unwrap();
+    let _ = interpret(&mut variables, "B = [1 0; 2 1; 3 0.5]".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "all(abs(A * (A \\ B) - B) < 1e-12)".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    // Other square systems
+    let (result, _) = interpret(&mut variables, "solve([0 1.5; 2 1], [3; 4])".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[1; 2]".to_string()).unwrap().0);
+
+    // Over-determined systems are solved by least squares, like the line through
+    // points on either side of `y = 2x + 1`
+    let _ = interpret(&mut variables, "x = [0; 1; 2; 3]".to_string()).unwrap();
+    let _ = interpret(&mut variables, "y = [1.1; 2.9; 5.1; 6.9]".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "all(abs([x [1; 1; 1; 1]] \\ y - [1.96; 1.06]) < 1e-12)".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    let (result, _) = interpret(&mut variables, "[2 0; 0 2i] \\ [2; 2]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[1; -1i]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "2 [s] \\ 6 [m]".to_string()).unwrap();
+    assert_eq!(result.to_string(), "3 m/s");
+
+    let code = "[1 2; 2 4.5] \\ [1 2]".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &ImproperDimensions);
+
+    let code = "[1 2; 2 4] \\ [1; 2]".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &SingularMatrix);
+
+    let code = "[1 2; 2 4; 3 6] \\ [1; 2; 3]".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &SingularMatrix);
+
+    // Least squares is only supported for real systems
+    let code = "[1 0; 0 1i; 1 1] \\ [1; 2; 3]".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &SquareMatrixRequired);
+}
+
+#[test]
+fn interpret_22() {
+    let mut variables = get_std_variables();
+
+    // Symmetric matrices have real eigenvalues and orthonormal eigenvectors
+    let _ = interpret(&mut variables, "K = [2 -1 0; -1 2 -1; 0 -1 2]".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "all(abs(eig(K) - [2 - sqrt(2); 2; 2 + sqrt(2)]) < 1e-12)".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    let _ = interpret(&mut variables, "V = eigvec(K)".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "all(abs(t(V)*V - I(3)) < 1e-12)".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+    let (result, _) = interpret(&mut variables, "all(abs(K*V[1:3, 1] - (2 - sqrt(2))*V[1:3, 1]) < 1e-12)".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    // General matrices
+    let _ = interpret(&mut variables, "A = [4 1 2; 2 3 0; 1 0.5 6]".to_string()).unwrap();
+    let _ = interpret(&mut variables, "e = eig(A); V = eigvec(A)".to_string()).unwrap();
+    for k in 1..=3 {
+        let code = format!("all(abs(A*V[1:3, {k}] - e[{k}]*V[1:3, {k}]) < 1e-9)");
+        let (result, _) = interpret(&mut variables, code).unwrap();
+        assert_eq!(result, Expression::Bool (true));
+    }
+    let (result, _) = interpret(&mut variables, "abs(e[1]*e[2]*e[3] - det(A)) < 1e-9".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    // Rotations have complex eigenvalues and eigenvectors
+    let (result, _) = interpret(&mut variables, "eig([0 1; -1 0])".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[-1i; 1i]".to_string()).unwrap().0);
+
+    let _ = interpret(&mut variables, "R = [1 -2; 2 1]; e = eig(R); V = eigvec(R)".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "all(abs(R*V[1:2, 2] - e[2]*V[1:2, 2]) < 1e-9)".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    let code = "eig([1 2 3])".to_string();
+    assert_eq!(interpret(&mut variables, code).unwrap_err().kind(), &SquareMatrixRequired);
+}
+
+#[test]
+fn interpret_23() {
+    let mut variables = get_std_variables();
+
+    let (result, _) = interpret(&mut variables, "svd([3 0; 0 -4])".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[4; 3]".to_string()).unwrap().0);
+
+    let (result, _) = interpret(&mut variables, "all(abs(svd([3 2 2; 2 3 -2]) - [5; 3]) < 1e-12)".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    let (result, _) = interpret(&mut variables, "rank([1 2 3; 4 5 6; 7 8 9])".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (2));
+
+    let (result, _) = interpret(&mut variables, "rank([1 2; 3 4; 5 6])".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (2));
+
+    // The pseudoinverse gives least-squares solutions
+    let (result, _) = interpret(&mut variables, "all(abs(pinv([1; 1]) - [0.5 0.5]) < 1e-12)".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    let _ = interpret(&mut variables, "A = [1 2; 3 4; 5 6]".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "all(abs(pinv(A)*A - I(2)) < 1e-12)".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    let _ = interpret(&mut variables, "N = null([1 2 3; 4 5 6; 7 8 9])".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "all(abs([1 2 3; 4 5 6; 7 8 9]*N) < 1e-12) && abs(t(N)*N - 1) < 1e-12".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    let (result, _) = interpret(&mut variables, "all(abs(null([1 1 1])'*[1; 1; 1]) < 1e-12)".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    let (result, _) = interpret(&mut variables, "all(abs(abs(orth([1 2; 2 4])) - [1; 2]/sqrt(5)) < 1e-12)".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    let (result, _) = interpret(&mut variables, "cond([1 0; 0 100])".to_string()).unwrap();
+    assert_eq!(result, Expression::Int (100));
+}
+
+#[test]
+fn interpret_24() {
+    let mut variables = get_std_variables();
+
+    let _ = interpret(&mut variables, "A = [1 2; 3 4; 5 6]".to_string()).unwrap();
+
+    let (result, _) = interpret(&mut variables, "[Q, R] = qr(A)".to_string()).unwrap();
+    assert_eq!(result, Expression::Nil);
+    let (result, _) = interpret(&mut variables, "all(abs(Q*R - A) < 1e-12) && all(abs(t(Q)*Q - I(3)) < 1e-12) && abs(R[3, 1]) + abs(R[3, 2]) + abs(R[2, 1]) == 0".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    // A single output is `R`
+    let (result, _) = interpret(&mut variables, "all(abs(qr(A) - R) < 1e-12)".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    // The economy factorization drops the columns of `Q` that multiply zero rows of `R`
+    let _ = interpret(&mut variables, "[Q R] = qr(A, 0)".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "all(abs(Q*R - A) < 1e-12) && all(abs(t(Q)*Q - I(2)) < 1e-12) && R[2, 1] == 0".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+    let (result, _) = interpret(&mut variables, "Q".to_string()).unwrap();
+    assert_eq!(result.to_matrix().unwrap().cols(), 2);
+
+    let (result, _) = interpret(&mut variables, "chol([4 2; 2 5])".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[2 0; 1 2]".to_string()).unwrap().0);
+
+    let result = interpret(&mut variables, "chol([1 2; 2 1])".to_string());
+    assert_eq!(result.unwrap_err().kind(), &NotPositiveDefinite);
+
+    let _ = interpret(&mut variables, "[L, U, P] = lu([1 2; 3 4])".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "P*[1 2; 3 4] == L*U".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[true true; true true]".to_string()).unwrap().0);
+    let (result, _) = interpret(&mut variables, "U".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[3 4; 0 2/3]".to_string()).unwrap().0);
+
+    let _ = interpret(&mut variables, "[V, D] = eig([2 1; 1 2])".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "all(abs([2 1; 1 2]*V - V*D) < 1e-12) && all(abs(D - [1 0; 0 3]) < 1e-12)".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    let _ = interpret(&mut variables, "[U, S, V] = svd([1 2; 2 4; 0 0])".to_string()).unwrap();
+    let (result, _) = interpret(&mut variables, "all(abs(U*S*t(V) - [1 2; 2 4; 0 0]) < 1e-12) && all(abs(t(U)*U - I(3)) < 1e-12) && all(abs(t(V)*V - I(2)) < 1e-12)".to_string()).unwrap();
+    assert_eq!(result, Expression::Bool (true));
+
+    let result = interpret(&mut variables, "[Q, R] = qr()".to_string());
+    assert_eq!(result.unwrap_err().kind(), &WrongNumberOfArgs);
+
+    let result = interpret(&mut variables, "[Q, R, X] = qr(A)".to_string());
+    assert_eq!(result.unwrap_err().kind(), &WrongNumberOfOutputs);
+
+    let result = interpret(&mut variables, "[X, Y] = A".to_string());
+    assert_eq!(result.unwrap_err().kind(), &WrongNumberOfOutputs);
+
+    // Matrix values may be separated by commas
+    let (result, _) = interpret(&mut variables, "[1, 2; 3, 4]".to_string()).unwrap();
+    assert_eq!(result, interpret(&mut variables, "[1 2; 3 4]".to_string()).unwrap().0);
+}
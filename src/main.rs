@@ -8,13 +8,13 @@ use std::{
     env,
     fs,
     process::exit,
-    collections::HashMap,
 };
 
 use colored::*;
 
 use elemental::{
-    interpret,
+    is_complete,
+    run,
     error::*,
     Expression,
     get_std_variables,
@@ -33,8 +33,8 @@ fn main() {
         None => unreachable!(), // This code is unreachable as we know the length of `env::args()` is at least 2
     };
 
-    let code: Vec<String> = match fs::read_to_string(input_file.to_owned()) {
-        Ok(c) => c.split("\n").map(|x| x.to_string()).collect::<Vec<String>>(),
+    let source = match fs::read_to_string(&input_file) {
+        Ok(c) => c,
         Err(_) => {
             throw(CouldNotReadFile (input_file));
             exit(0);
@@ -42,25 +42,25 @@ fn main() {
     };
 
     // Store a list of variables in the program
-    let mut variables = HashMap::from([
-        ("pi".to_string(), Expression::Float (3.141592653)),
-        ("deg".to_string(), Expression::Float (3.141592653/180.0)),
-    ]);
-
-    for mut command in code {
-        // For the tokenizer to work, we add `\n` to each line
-        command.push('\n');
-
-        let (expression, is_silent) = interpret(&mut variables, command.to_owned());
+    let mut variables = get_std_variables();
 
-        // Only if it is not "silent", print the input and output
-        if !is_silent {
+    // Run the whole file as a program, printing the value of each statement
+    // unless it is "silent"
+    let result = run(&mut variables, source.to_owned(), |statement, expression| {
+        if !statement.is_silent() && *expression != Expression::Nil {
+            let command = statement.get_span().excerpt(&source);
             let output = format!(
                 "{}",
                 expression,
             );
-            println!("\n{}\n=\n\n{}\n", command, output);
+            println!("\n{}\n\n=\n\n{}\n", command, output);
         }
+    });
+
+    // Stop executing the script at the first error
+    if let Err(e) = result {
+        println!("{}", e.render(&source, Some(&input_file)));
+        exit(1);
     }
 }
 
@@ -84,20 +84,43 @@ fn interpreter() -> ! {
         };
 
         match stdin.read_line(&mut input) {
+            // Stop at the end of the input
+            Ok(0) => {
+                println!();
+                exit(0);
+            },
             Ok(_) => (),
             Err(_) => throw(CouldNotReadStdin),
         };
 
-        let (expression, is_silent) = interpret(&mut variables, input.to_owned());
+        // Continue reading until every parenthesis, bracket and brace is closed
+        while !is_complete(&input) {
+            print!("... ");
+            match stdout.flush() {
+                Ok(_) => (),
+                Err(_) => throw(CouldNotFlushOutput),
+            };
+
+            match stdin.read_line(&mut input) {
+                Ok(0) => break,
+                Ok(_) => (),
+                Err(_) => throw(CouldNotReadStdin),
+            };
+        }
 
-        // Only if it is not "silent", display output
-        if !is_silent {
-            let output = format!(
-                "{}",
-                expression,
-            );
-            dbg!(&expression);
-            println!("\n{}\n", output);
+        // Display the value of each statement that is not "silent"
+        let result = run(&mut variables, input.to_owned(), |statement, expression| {
+            if !statement.is_silent() && *expression != Expression::Nil {
+                let output = format!(
+                    "{}",
+                    expression,
+                );
+                println!("\n{}\n", output);
+            }
+        });
+
+        if let Err(e) = result {
+            println!("{}", e.render(&input, None));
         }
 
         input.clear();
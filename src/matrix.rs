@@ -8,19 +8,24 @@ use std::ops::{
     IndexMut,
 };
 
+use num_complex::Complex64;
+use num_traits::Num;
+
 use crate::error::*;
 
 /// Abstracts over matrices.
+/// 
+/// Values are real (`f64`) unless otherwise specified, as in `Matrix<Complex64>`.
 #[derive(Clone, Debug)]
-pub struct Matrix {
+pub struct Matrix<T = f64> {
     rows: usize,
     cols: usize,
-    vals: Vec<f64>,
+    vals: Vec<T>,
 }
 
-impl Matrix {
+impl<T: Clone + Num> Matrix<T> {
     /// Constructs a new matrix.
-    pub fn new(rows: usize, cols: usize, vals: Vec<f64>) -> Self {
+    pub fn new(rows: usize, cols: usize, vals: Vec<T>) -> Self {
         Self {
             rows,
             cols,
@@ -29,8 +34,8 @@ impl Matrix {
     }
 
     /// Multiplies the given matrix by the given scalar, returning a new matrix.
-    pub fn scalar_multiply(&self, scalar: f64) -> Self {
-        let vals = self.vals.iter().map(|x| scalar*x).collect::<Vec<f64>>();
+    pub fn scalar_multiply(&self, scalar: T) -> Self {
+        let vals = self.vals.iter().map(|x| scalar.clone()*x.clone()).collect::<Vec<T>>();
 
         Self {
             vals,
@@ -49,17 +54,17 @@ impl Matrix {
     }
 
     /// Copies the values of the matrix.
-    pub fn copy_vals(&self) -> Vec<f64> {
+    pub fn copy_vals(&self) -> Vec<T> {
         self.vals.to_owned()
     }
 
     /// Gets the values of the matrix.
-    pub fn vals(&self) -> &Vec<f64> {
+    pub fn vals(&self) -> &Vec<T> {
         &self.vals
     }
     
     /// Gets the values of the matrix, with mutable permission.
-    pub fn vals_mut(&mut self) -> &mut Vec<f64> {
+    pub fn vals_mut(&mut self) -> &mut Vec<T> {
         &mut self.vals
     }
 
@@ -73,65 +78,89 @@ impl Matrix {
     }
 }
 
+impl Matrix {
+    /// Converts this matrix into a complex matrix with no imaginary part.
+    pub fn to_complex(&self) -> Matrix<Complex64> {
+        let vals = self.vals.iter().map(|x| Complex64::new(*x, 0.0)).collect::<Vec<Complex64>>();
+
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            vals,
+        }
+    }
+}
+
 /// Defines matrix addition.
-impl Add for Matrix {
-    type Output = Self;
+impl<T: Clone + Num> Add for Matrix<T> {
+    type Output = Result<Self, Error>;
 
-    fn add(self, other: Self) -> Self {
+    fn add(self, other: Self) -> Result<Self, Error> {
         if self.rows() != other.rows() || self.cols() != other.cols() {
-            throw(ImproperDimensions);
-            Self::new(0, 0, Vec::new());
+            return Err(ImproperDimensions);
         }
 
         let mut output_vals = Vec::new();
 
         for (i, j) in self.vals().iter().zip(other.vals().iter()) {
-            output_vals.push(i + j);
+            output_vals.push(i.clone() + j.clone());
         }
 
-        Self {
+        Ok(Self {
             vals: output_vals,
             ..self
-        }
+        })
     }
 }
 
 /// Defines matrix subtraction.
-impl Sub for Matrix {
-    type Output = Self;
+impl<T: Clone + Num> Sub for Matrix<T> {
+    type Output = Result<Self, Error>;
 
-    fn sub(self, other: Self) -> Self {
+    fn sub(self, other: Self) -> Result<Self, Error> {
         if self.rows() != other.rows() || self.cols() != other.cols() {
-            throw(ImproperDimensions);
-            Self::new(0, 0, Vec::new());
+            return Err(ImproperDimensions);
         }
 
         let mut output_vals = Vec::new();
 
         for (i, j) in self.vals().iter().zip(other.vals().iter()) {
-            output_vals.push(i - j);
+            output_vals.push(i.clone() - j.clone());
         }
 
-        Self {
+        Ok(Self {
             vals: output_vals,
             ..self
-        }
+        })
     }
 }
 
 /// Defines matrix multiplication.
-impl Mul for Matrix {
-    type Output = Self;
+impl<T: Clone + Num> Mul for Matrix<T> {
+    type Output = Result<Self, Error>;
+
+    fn mul(self, other: Self) -> Result<Self, Error> {
+        if self.cols() != other.rows() {
+            return Err(ImproperDimensions);
+        }
+
+        let mut output = Self::new(self.rows(), other.cols(), vec![T::zero(); self.rows()*other.cols()]);
+
+        for i in 0..self.rows() {
+            for j in 0..other.cols() {
+                for k in 0..self.cols() {
+                    output[[i, j]] = output[[i, j]].clone() + self[[i, k]].clone()*other[[k, j]].clone();
+                }
+            }
+        }
 
-    #[allow(unused_variables)]
-    fn mul(self, other: Self) -> Self {
-        Self::new(0, 0, Vec::new())
+        Ok(output)
     }
 }
 
 /// Defines matrix indexing.
-impl Index<[usize; 2]> for Matrix {
-    type Output = f64;
+impl<T: Clone + Num> Index<[usize; 2]> for Matrix<T> {
+    type Output = T;
 
     fn index(&self, index: [usize; 2]) -> &Self::Output {
         let i = index[0];
@@ -141,7 +170,7 @@ impl Index<[usize; 2]> for Matrix {
 }
 
 /// Defines matrix indexing with mutable permission.
-impl IndexMut<[usize; 2]> for Matrix {
+impl<T: Clone + Num> IndexMut<[usize; 2]> for Matrix<T> {
     fn index_mut(&mut self, index: [usize; 2]) -> &mut Self::Output {
         let i = index[0];
         let j = index[1];
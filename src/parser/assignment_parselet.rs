@@ -6,20 +6,75 @@ use crate::error::*;
 pub struct AssignmentParselet;
 
 impl InfixParselet for AssignmentParselet {
-    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, _token: Token, left: Expression) -> Expression {
-        let right = parser.parse(tokenizer, 0);
-
-        let identifier = match left {
-            Expression::Identifier (s) => s,
-            _ => {
-                throw(ExpectedIdentifier);
-                return Expression::Nil;
+    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, token: Token, left: Expression) -> Result<Expression, Error> {
+        let right = parser.parse(tokenizer, 0)?;
+
+        match left {
+            Expression::Identifier (s) => Ok(Expression::Assignment {
+                identifier: s,
+                value: Box::new(right),
+            }),
+
+            // Function definitions look like `f(x, y) = ...`
+            Expression::Call {
+                name,
+                args,
+                span,
+            } => {
+                let mut params = Vec::new();
+                for arg in args {
+                    match arg {
+                        Expression::Identifier (p) => params.push(p),
+                        _ => return Err(ExpectedIdentifier.with_span(span)),
+                    }
+                }
+
+                Ok(Expression::Assignment {
+                    identifier: name,
+                    value: Box::new(Expression::Function {
+                        params,
+                        body: Box::new(right),
+                    }),
+                })
+            },
+
+            // Indexed assignments look like `A[i, j] = ...`
+            Expression::Index {
+                target,
+                indices,
+                span,
+            } => match *target {
+                Expression::Identifier (s) => Ok(Expression::IndexedAssignment {
+                    identifier: s,
+                    indices,
+                    value: Box::new(right),
+                    span,
+                }),
+                _ => Err(ExpectedIdentifier.with_span(span)),
+            },
+
+            // Multiple assignments look like `[Q, R] = ...`
+            Expression::Matrix {
+                rows: 1,
+                values,
+                ..
+            } => {
+                let mut identifiers = Vec::new();
+                for value in values {
+                    match value {
+                        Expression::Identifier (s) => identifiers.push(s),
+                        _ => return Err(ExpectedIdentifier.with_span(token.get_span())),
+                    }
+                }
+
+                Ok(Expression::MultipleAssignment {
+                    identifiers,
+                    value: Box::new(right),
+                    span: token.get_span(),
+                })
             },
-        };
 
-        Expression::Assignment {
-            identifier,
-            value: Box::new(right),
+            _ => Err(ExpectedIdentifier.with_span(token.get_span())),
         }
     }
-}
\ No newline at end of file
+}
@@ -3,24 +3,32 @@
 use crate::parselet_utils::*;
 use crate::error::*;
 
+use super::is_right_associative;
+
 pub struct BinOpParselet;
 
 impl InfixParselet for BinOpParselet {
-    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, token: Token, left: Expression) -> Expression {
-        let _peek = match tokenizer.peek() {
-            Some(t) => t,
-            None => {
-                throw(UnexpectedEof);
-                return Expression::Nil;
-            },
+    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, token: Token, left: Expression) -> Result<Expression, Error> {
+        if tokenizer.peek().is_none() {
+            return Err(UnexpectedEof.with_span(tokenizer.eof_span()));
+        }
+
+        // Right-associative operators bind the right-hand side more loosely,
+        // allowing another instance of the same operator to be parsed there
+        let precedence: u8 = token.get_class().into();
+        let precedence = if is_right_associative(token.get_class()) {
+            precedence - 1
+        } else {
+            precedence
         };
 
-        let right = parser.parse(tokenizer, token.get_class().into());
+        let right = parser.parse(tokenizer, precedence)?;
 
-        Expression::BinOp {
+        Ok(Expression::BinOp {
             left: Box::new(left),
             op: token.get_value(),
             right: Box::new(right),
-        }
+            span: token.get_span(),
+        })
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,66 @@
+//! A parselet for blocks delimited by braces, such as the branches of `if`
+//! and the bodies of loops.
+//! 
+//! A block holds any number of statements, separated by semicolons or newlines.
+
+use crate::parselet_utils::*;
+use crate::error::*;
+
+pub struct BlockParselet;
+
+impl BlockParselet {
+    /// Parses a block, starting with the opening brace.
+    /// 
+    /// The opening brace may be on the next line.
+    pub fn parse_block(parser: &Parser, tokenizer: &mut Tokenizer) -> Result<Expression, Error> {
+        tokenizer.skip_newlines();
+
+        match tokenizer.next() {
+            Some(t) if t.check(TokenClass::OpenBrace) => Self.parse(parser, tokenizer, t),
+            Some(t) => Err(ExpectedOpenBrace.with_span(t.get_span())),
+            None => Err(ExpectedOpenBrace.with_span(tokenizer.eof_span())),
+        }
+    }
+
+    /// Parses the contents of a block, up to and including the closing brace.
+    fn parse_contents(parser: &Parser, tokenizer: &mut Tokenizer) -> Result<Expression, Error> {
+        let mut statements = Vec::new();
+
+        loop {
+            match tokenizer.peek() {
+                Some(t) if t.check(TokenClass::CloseBrace) => {
+                    tokenizer.next();
+                    break;
+                },
+                Some(t) if t.check(TokenClass::Semicolon) || t.check(TokenClass::Newline) => {
+                    tokenizer.next();
+                },
+                Some(_) => {
+                    statements.push(parser.parse(tokenizer, 0)?);
+
+                    // Each statement must end with a semicolon, a newline, or the closing brace
+                    match tokenizer.peek() {
+                        Some(t) if t.check(TokenClass::Semicolon) || t.check(TokenClass::Newline) || t.check(TokenClass::CloseBrace) => (),
+                        Some(t) => return Err(UnexpectedEof.with_span(t.get_span())),
+                        None => return Err(ExpectedCloseBrace.with_span(tokenizer.eof_span())),
+                    }
+                },
+                None => return Err(ExpectedCloseBrace.with_span(tokenizer.eof_span())),
+            }
+        }
+
+        Ok(Expression::Block {
+            statements,
+        })
+    }
+}
+
+impl PrefixParselet for BlockParselet {
+    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, _token: Token) -> Result<Expression, Error> {
+        tokenizer.open(TokenClass::OpenBrace);
+        let result = Self::parse_contents(parser, tokenizer);
+        tokenizer.close();
+
+        result
+    }
+}
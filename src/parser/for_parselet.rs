@@ -0,0 +1,34 @@
+//! A parselet for loops such as `for i in 1:10 { s = s + i }`.
+
+use crate::parselet_utils::*;
+use crate::error::*;
+
+use super::block_parselet::BlockParselet;
+
+pub struct ForParselet;
+
+impl PrefixParselet for ForParselet {
+    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, token: Token) -> Result<Expression, Error> {
+        let variable = match tokenizer.next() {
+            Some(t) if t.check(TokenClass::Identifier) => t.get_value(),
+            Some(t) => return Err(ExpectedIdentifier.with_span(t.get_span())),
+            None => return Err(ExpectedIdentifier.with_span(tokenizer.eof_span())),
+        };
+
+        match tokenizer.next() {
+            Some(t) if t.check(TokenClass::In) => (),
+            Some(t) => return Err(ExpectedIn.with_span(t.get_span())),
+            None => return Err(ExpectedIn.with_span(tokenizer.eof_span())),
+        }
+
+        let iterable = parser.parse(tokenizer, 0)?;
+        let body = BlockParselet::parse_block(parser, tokenizer)?;
+
+        Ok(Expression::For {
+            variable,
+            iterable: Box::new(iterable),
+            body: Box::new(body),
+            span: token.get_span(),
+        })
+    }
+}
@@ -1,32 +1,34 @@
 //! A parselet for function calls.
 
 use crate::parselet_utils::*;
+use crate::parser::UnitParselet;
 use crate::error::*;
 
 pub struct FuncParselet;
 
-impl InfixParselet for FuncParselet {
-    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, _token: Token, left: Expression) -> Expression {
+impl FuncParselet {
+    /// Parses the arguments of a function call, up to and including the closing parenthesis.
+    fn parse_args(parser: &Parser, tokenizer: &mut Tokenizer, token: Token, left: Expression) -> Result<Expression, Error> {
         if let Expression::Identifier (s) = left {
+            // The call starts at the name of the function, just before the parenthesis
+            let start = tokenizer.lookbehind(2).map_or(token.get_span(), |t| t.get_span());
+
             let mut args = Vec::new();
 
             // Construct a list of function arguments
 
             let mut current = match tokenizer.peek() {
                 Some(t) => t,
-                None => {
-                    throw(UnexpectedEof);
-                    return Expression::Nil;
-                },
+                None => return Err(UnexpectedEof.with_span(tokenizer.eof_span())),
             };
 
             while current.get_class() != TokenClass::CloseParen {
-                let arg = parser.parse(tokenizer, current.get_class().into());
+                let arg = parser.parse(tokenizer, 0)?;
                 args.push(arg);
 
                 current = match tokenizer.peek() {
                     Some(t) => t,
-                    None => break,
+                    None => return Err(ExpectedCloseParen.with_span(tokenizer.eof_span())),
                 };
 
                 if current.get_class() == TokenClass::Comma {
@@ -35,15 +37,26 @@ impl InfixParselet for FuncParselet {
             }
 
             // Consume the closing parenthesis
+            let span = start.to(current.get_span());
             tokenizer.next();
 
-            Expression::Call {
+            Ok(Expression::Call {
                 name: s,
                 args,
-            }
+                span,
+            })
         } else {
-            throw(ExpectedIdentifier);
-            return Expression::Nil;
+            Err(ExpectedIdentifier.with_span(token.get_span()))
         }
     }
-}
\ No newline at end of file
+}
+
+impl InfixParselet for FuncParselet {
+    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, token: Token, left: Expression) -> Result<Expression, Error> {
+        tokenizer.open(TokenClass::OpenParen);
+        let result = Self::parse_args(parser, tokenizer, token, left);
+        tokenizer.close();
+
+        UnitParselet::parse_brackets(tokenizer, result?)
+    }
+}
@@ -1,11 +1,12 @@
 //! A parselet for identifiers.
 
 use crate::parselet_utils::*;
+use crate::error::*;
 
 pub struct IdentifierParselet;
 
 impl PrefixParselet for IdentifierParselet {
-    fn parse(&self, _parser: &Parser, _tokenizer: &mut Tokenizer, token: Token) -> Expression {
-        Expression::Identifier (token.get_value())
+    fn parse(&self, _parser: &Parser, _tokenizer: &mut Tokenizer, token: Token) -> Result<Expression, Error> {
+        Ok(Expression::Identifier (token.get_value()))
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,42 @@
+//! A parselet for conditionals such as `if x > 0 { x } else { -x }`.
+
+use crate::parselet_utils::*;
+use crate::error::*;
+
+use super::block_parselet::BlockParselet;
+
+pub struct IfParselet;
+
+impl PrefixParselet for IfParselet {
+    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, token: Token) -> Result<Expression, Error> {
+        let condition = parser.parse(tokenizer, 0)?;
+        let then = BlockParselet::parse_block(parser, tokenizer)?;
+
+        // The `else` branch is optional, and may itself be another conditional
+        // It may also begin on the next line
+        if tokenizer.peek_past_newlines() == Some (TokenClass::Else) {
+            tokenizer.skip_newlines();
+        }
+
+        let otherwise = match tokenizer.peek() {
+            Some(t) if t.check(TokenClass::Else) => {
+                tokenizer.next();
+                match tokenizer.peek() {
+                    Some(t) if t.check(TokenClass::If) => {
+                        tokenizer.next();
+                        Some (Box::new(self.parse(parser, tokenizer, t)?))
+                    },
+                    _ => Some (Box::new(BlockParselet::parse_block(parser, tokenizer)?)),
+                }
+            },
+            _ => None,
+        };
+
+        Ok(Expression::Conditional {
+            condition: Box::new(condition),
+            then: Box::new(then),
+            otherwise,
+            span: token.get_span(),
+        })
+    }
+}
@@ -0,0 +1,75 @@
+//! A parselet for matrix indexing and slicing.
+
+use crate::parselet_utils::*;
+use crate::error::*;
+
+pub struct IndexParselet;
+
+impl IndexParselet {
+    /// Parses a single index, which may be an expression (including a range such
+    /// as `1:3`) or a lone colon (selecting everything, like `1:end`).
+    fn parse_index(parser: &Parser, tokenizer: &mut Tokenizer) -> Result<Expression, Error> {
+        let current = match tokenizer.peek() {
+            Some(t) => t,
+            None => return Err(ExpectedCloseBracket.with_span(tokenizer.eof_span())),
+        };
+
+        // A lone colon selects the entire dimension
+        if current.check(TokenClass::Colon) {
+            let lone = match tokenizer.lookahead(1) {
+                Some(t) => t.check(TokenClass::Comma) || t.check(TokenClass::CloseBracket),
+                None => false,
+            };
+
+            if lone {
+                tokenizer.next();
+                return Ok(Expression::Range {
+                    start: Box::new(Expression::Int (1)),
+                    step: None,
+                    end: Box::new(Expression::Identifier ("end".to_string())),
+                    span: current.get_span(),
+                });
+            }
+        }
+
+        parser.parse(tokenizer, 0)
+    }
+
+    /// Parses the indices of a matrix, up to and including the closing bracket.
+    fn parse_indices(parser: &Parser, tokenizer: &mut Tokenizer, token: Token, left: Expression) -> Result<Expression, Error> {
+        let mut indices = Vec::new();
+
+        loop {
+            indices.push(Self::parse_index(parser, tokenizer)?);
+
+            let current = match tokenizer.next() {
+                Some(t) => t,
+                None => return Err(ExpectedCloseBracket.with_span(tokenizer.eof_span())),
+            };
+
+            match current.get_class() {
+                TokenClass::Comma => continue,
+                TokenClass::CloseBracket => {
+                    return Ok(Expression::Index {
+                        target: Box::new(left),
+                        indices,
+                        span: token.get_span().to(current.get_span()),
+                    });
+                },
+                _ => return Err(ExpectedCloseBracket.with_span(current.get_span())),
+            }
+        }
+    }
+}
+
+impl InfixParselet for IndexParselet {
+    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, token: Token, left: Expression) -> Result<Expression, Error> {
+        // Indices are separated by commas, like function arguments, rather than
+        // by whitespace, like matrix values
+        tokenizer.open(TokenClass::OpenParen);
+        let result = Self::parse_indices(parser, tokenizer, token, left);
+        tokenizer.close();
+
+        result
+    }
+}
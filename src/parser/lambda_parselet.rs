@@ -0,0 +1,31 @@
+//! A parselet for lambdas (anonymous functions) such as `x -> x + 1`.
+
+use crate::parselet_utils::*;
+use crate::error::*;
+
+pub struct LambdaParselet;
+
+impl LambdaParselet {
+    /// Parses the body of a lambda with the given parameters.
+    /// 
+    /// Lambdas are right-associative, so `x -> y -> x + y` is `x -> (y -> x + y)`.
+    pub fn parse_body(parser: &Parser, tokenizer: &mut Tokenizer, params: Vec<String>) -> Result<Expression, Error> {
+        let body = parser.parse(tokenizer, u8::from(TokenClass::Arrow) - 1)?;
+
+        Ok(Expression::Lambda {
+            params,
+            body: Box::new(body),
+        })
+    }
+}
+
+impl InfixParselet for LambdaParselet {
+    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, token: Token, left: Expression) -> Result<Expression, Error> {
+        let param = match left {
+            Expression::Identifier (s) => s,
+            _ => return Err(ExpectedIdentifier.with_span(token.get_span())),
+        };
+
+        Self::parse_body(parser, tokenizer, vec![param])
+    }
+}
@@ -1,17 +1,31 @@
 //! A parselet for numeric literals.
 
+use num_bigint::BigInt;
+use num_complex::Complex64;
+use num_rational::BigRational;
+
 use crate::parselet_utils::*;
+use crate::parser::UnitParselet;
+use crate::error::*;
 
 pub struct LiteralParselet;
 
 impl PrefixParselet for LiteralParselet {
-    fn parse(&self, _parser: &Parser, _tokenizer: &mut Tokenizer, token: Token) -> Expression {
+    fn parse(&self, _parser: &Parser, tokenizer: &mut Tokenizer, token: Token) -> Result<Expression, Error> {
         // Note: it's ok to use `Result::unwrap()` here because we checked that
         // the token's value parses to valid data during tokenization.
-        match token.get_class() {
-            TokenClass::Int => Expression::Int (str::parse::<i64>(&token.get_value()).unwrap()),
+        let value = match token.get_class() {
+            TokenClass::Int => match str::parse::<i64>(&token.get_value()) {
+                Ok(i) => Expression::Int (i),
+
+                // Integers too large for `i64` are kept exact
+                Err(_) => Expression::Rational (BigRational::from_integer(str::parse::<BigInt>(&token.get_value()).unwrap())),
+            },
             TokenClass::Float => Expression::Float (str::parse::<f64>(&token.get_value()).unwrap()),
+            TokenClass::Imaginary => Expression::Complex (Complex64::new(0.0, str::parse::<f64>(&token.get_value()).unwrap())),
             _ => unreachable!(),
-        }
+        };
+
+        UnitParselet::parse_annotation(tokenizer, value)
     }
-}
\ No newline at end of file
+}
@@ -1,94 +1,90 @@
 //! A parselet for matrix (and vector) input.
 
 use crate::parselet_utils::*;
+use crate::parser::UnitParselet;
 use crate::error::*;
 
 pub struct MatrixParselet;
 
-impl PrefixParselet for MatrixParselet {
-    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, _token: Token) -> Expression {
-        // Create a new vector to hold matrix/vector values
-        let mut values: Vec<Expression> = Vec::new();
-
-        // Create a variable to hold column count
-        let mut cols: usize = 0;
-
-        let mut current = match tokenizer.peek() {
-            Some(t) => t,
-            None => {
-                throw(UnexpectedEof);
-                return Expression::Nil;
-            },
-        };
+impl MatrixParselet {
+    /// Parses the values of a matrix, up to and including the closing bracket.
+    fn parse_values(parser: &Parser, tokenizer: &mut Tokenizer, token: Token) -> Result<Expression, Error> {
+        // Create a new vector to hold the values of each row
+        // Note: rows end on a semicolon or a newline
+        let mut rows: Vec<Vec<Expression>> = vec![Vec::new()];
 
-        // Count the number of values in the first row (number of columns in the matrix)
-        // Note: rows end on a semicolon
-        while current.get_class() != TokenClass::Semicolon {
-            let expr = parser.parse(tokenizer, 0);
-            values.push(expr);
-            cols += 1;
-
-            current = match tokenizer.peek() {
+        let span = loop {
+            let current = match tokenizer.peek() {
                 Some(t) => t,
-                None => {
-                    throw(UnexpectedEof);
-                    return Expression::Nil;
-                },
+                None => return Err(UnexpectedEof.with_span(tokenizer.eof_span())),
             };
-            if current.get_class() == TokenClass::CloseBracket {
-                break;
-            }
-        }
 
-        match tokenizer.next() {
-            Some(t) => match t.get_class() {
-                // This is a vector
-                TokenClass::CloseBracket => return Expression::Matrix {
-                    rows: 1,
-                    cols,
-                    values,
+            match current.get_class() {
+                TokenClass::CloseBracket => {
+                    tokenizer.next();
+                    break token.get_span().to(current.get_span());
                 },
-                _ => (),
-            },
-            // This is also a vector
-            None => return Expression::Matrix {
-                rows: 1,
-                cols,
-                values,
-            },
+                TokenClass::Semicolon | TokenClass::Newline => {
+                    tokenizer.next();
+                    rows.push(Vec::new());
+                },
+
+                // Values in a row may be separated by commas, as in `[Q, R]`
+                TokenClass::Comma => {
+                    tokenizer.next();
+                },
+                _ => {
+                    let expr = parser.parse(tokenizer, 0)?;
+                    rows.last_mut().unwrap().push(expr);
+                },
+            }
         };
 
-        current = match tokenizer.peek() {
-            Some(t) => t,
+        // Discard empty rows, such as the one after a trailing semicolon
+        let rows = rows.into_iter().filter(|r| !r.is_empty()).collect::<Vec<Vec<Expression>>>();
 
-            // We already checked this above
-            None => unreachable!()
+        let cols = match rows.first() {
+            Some(r) => r.len(),
+            None => return Err(ImproperDimensions.with_span(span)),
         };
 
-        // Fill the remainder of the matrix
-        while current.get_class() != TokenClass::CloseBracket {
-            let expr = parser.parse(tokenizer, 0);
-            values.push(expr);
+        // Rows with different numbers of values, like `[A b; 0:1]`, can only be
+        // made consistent once their values are concatenated, so each row
+        // is kept as a row vector of its own
+        if rows.iter().any(|r| r.len() != cols) {
+            // If every value is a number, the rows can never be made consistent
+            let literal = |x: &Expression| matches!(x, Expression::Int (_) | Expression::Float (_) | Expression::Complex (_));
+            if rows.iter().flatten().all(literal) {
+                return Err(ImproperDimensions.with_span(span));
+            }
 
-            current = match tokenizer.peek() {
-                Some(t) => t,
-                None => break,
-            };
+            let values = rows.into_iter().map(|r| Expression::Matrix {
+                rows: 1,
+                cols: r.len(),
+                values: r,
+            }).collect::<Vec<Expression>>();
 
-            // Discard semicolons
-            if current.get_class() == TokenClass::Semicolon {
-                tokenizer.next();
-            }
+            return Ok(Expression::Matrix {
+                rows: values.len(),
+                cols: 1,
+                values,
+            });
         }
 
-        tokenizer.next();
+        Ok(Expression::Matrix {
+            rows: rows.len(),
+            cols,
+            values: rows.into_iter().flatten().collect(),
+        })
+    }
+}
 
-        let rows = values.len()/cols as usize;
+impl PrefixParselet for MatrixParselet {
+    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, token: Token) -> Result<Expression, Error> {
+        tokenizer.open(TokenClass::OpenBracket);
+        let result = Self::parse_values(parser, tokenizer, token);
+        tokenizer.close();
 
-        Expression::Matrix {
-            rows,
-            cols,
-            values,
-        }
+        UnitParselet::parse_annotation(tokenizer, result?)
     }
-}
\ No newline at end of file
+}
@@ -8,15 +8,26 @@ mod paren_parselet;
 mod matrix_parselet;
 mod func_parselet;
 mod prime_parselet;
+mod lambda_parselet;
+mod unary_parselet;
+mod index_parselet;
+mod range_parselet;
+mod block_parselet;
+mod if_parselet;
+mod for_parselet;
+mod while_parselet;
+mod unit_parselet;
 
 use std::collections::HashMap;
 
 use crate::{
     Expression,
+    Span,
     Token,
     TokenClass,
     Tokenizer,
 };
+use crate::error::*;
 
 use identifier_parselet::IdentifierParselet;
 use literal_parselet::LiteralParselet;
@@ -26,6 +37,15 @@ use paren_parselet::ParenParselet;
 use matrix_parselet::MatrixParselet;
 use func_parselet::FuncParselet;
 use prime_parselet::PrimeParselet;
+use lambda_parselet::LambdaParselet;
+use unary_parselet::UnaryParselet;
+use index_parselet::IndexParselet;
+use range_parselet::RangeParselet;
+use block_parselet::BlockParselet;
+use if_parselet::IfParselet;
+use for_parselet::ForParselet;
+use while_parselet::WhileParselet;
+use unit_parselet::UnitParselet;
 
 
 /// Converts a token class into a precedence value.
@@ -33,27 +53,79 @@ impl From<TokenClass> for u8 {
     fn from(t: TokenClass) -> u8 {
         match t {
             TokenClass::Assignment => 1,
-            TokenClass::Plus => 3,
-            TokenClass::Minus => 3,
-            TokenClass::Multiply => 4,
-            TokenClass::Divide => 4,
-            TokenClass::OpenParen => 6,
-            TokenClass::OpenBracket => 7,
-            TokenClass::Prime => 8,
+            TokenClass::Arrow => 2,
+            TokenClass::Or => 3,
+            TokenClass::And => 4,
+            TokenClass::Eq => 5,
+            TokenClass::NotEq => 5,
+            TokenClass::Less => 5,
+            TokenClass::Greater => 5,
+            TokenClass::LessEq => 5,
+            TokenClass::GreaterEq => 5,
+            TokenClass::Colon => 6,
+            TokenClass::Plus => 7,
+            TokenClass::Minus => 7,
+            TokenClass::PlusMinus => 7,
+            TokenClass::Multiply => 8,
+            TokenClass::Divide => 8,
+            TokenClass::Backslash => 8,
+            TokenClass::DotMultiply => 8,
+            TokenClass::DotDivide => 8,
+            TokenClass::Caret => 9,
+            TokenClass::DotCaret => 9,
+            TokenClass::OpenParen => 10,
+            TokenClass::OpenBracket => 11,
+            TokenClass::Prime => 12,
             _ => 0,
         }
     }
 }
 
 
+/// Checks whether or not an operator is right-associative.
+/// 
+/// For example, `2^3^2` is `2^(3^2)`, and likewise for `.^`.
+pub fn is_right_associative(t: TokenClass) -> bool {
+    matches!(t, TokenClass::Caret | TokenClass::DotCaret)
+}
+
+
 /// Abstracts over "prefix parselets".
 pub trait PrefixParselet {
-    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, token: Token) -> Expression;
+    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, token: Token) -> Result<Expression, Error>;
 }
 
 /// Abstracts over "infix parselets".
 pub trait InfixParselet {
-    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, token: Token, left: Expression) -> Expression;
+    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, token: Token, left: Expression) -> Result<Expression, Error>;
+}
+
+
+/// Holds a single statement of a program.
+#[derive(Clone, Debug)]
+pub struct Statement {
+    expression: Expression,
+    span: Span,
+    is_silent: bool,
+}
+
+impl Statement {
+    /// Gets the expression of the statement.
+    pub fn get_expression(&self) -> &Expression {
+        &self.expression
+    }
+
+    /// Gets the location of the statement in the source.
+    pub fn get_span(&self) -> Span {
+        self.span
+    }
+
+    /// Checks whether or not the statement ends with a semicolon.
+    /// 
+    /// The values of statements that end with semicolons are not displayed.
+    pub fn is_silent(&self) -> bool {
+        self.is_silent
+    }
 }
 
 
@@ -73,15 +145,40 @@ impl Parser {
         prefix_parselets.insert(TokenClass::Identifier, Box::new(IdentifierParselet {}));
         prefix_parselets.insert(TokenClass::Int, Box::new(LiteralParselet {}));
         prefix_parselets.insert(TokenClass::Float, Box::new(LiteralParselet {}));
+        prefix_parselets.insert(TokenClass::Imaginary, Box::new(LiteralParselet {}));
         prefix_parselets.insert(TokenClass::OpenParen, Box::new(ParenParselet {}));
         prefix_parselets.insert(TokenClass::OpenBracket, Box::new(MatrixParselet {}));
+        prefix_parselets.insert(TokenClass::Plus, Box::new(UnaryParselet {}));
+        prefix_parselets.insert(TokenClass::Minus, Box::new(UnaryParselet {}));
+        prefix_parselets.insert(TokenClass::Not, Box::new(UnaryParselet {}));
+        prefix_parselets.insert(TokenClass::OpenBrace, Box::new(BlockParselet {}));
+        prefix_parselets.insert(TokenClass::If, Box::new(IfParselet {}));
+        prefix_parselets.insert(TokenClass::For, Box::new(ForParselet {}));
+        prefix_parselets.insert(TokenClass::While, Box::new(WhileParselet {}));
         infix_parselets.insert(TokenClass::Assignment, Box::new(AssignmentParselet {}));
         infix_parselets.insert(TokenClass::Plus, Box::new(BinOpParselet {}));
         infix_parselets.insert(TokenClass::Minus, Box::new(BinOpParselet {}));
+        infix_parselets.insert(TokenClass::PlusMinus, Box::new(BinOpParselet {}));
         infix_parselets.insert(TokenClass::Multiply, Box::new(BinOpParselet {}));
         infix_parselets.insert(TokenClass::Divide, Box::new(BinOpParselet {}));
+        infix_parselets.insert(TokenClass::Backslash, Box::new(BinOpParselet {}));
+        infix_parselets.insert(TokenClass::Caret, Box::new(BinOpParselet {}));
+        infix_parselets.insert(TokenClass::DotMultiply, Box::new(BinOpParselet {}));
+        infix_parselets.insert(TokenClass::DotDivide, Box::new(BinOpParselet {}));
+        infix_parselets.insert(TokenClass::DotCaret, Box::new(BinOpParselet {}));
+        infix_parselets.insert(TokenClass::Eq, Box::new(BinOpParselet {}));
+        infix_parselets.insert(TokenClass::NotEq, Box::new(BinOpParselet {}));
+        infix_parselets.insert(TokenClass::Less, Box::new(BinOpParselet {}));
+        infix_parselets.insert(TokenClass::Greater, Box::new(BinOpParselet {}));
+        infix_parselets.insert(TokenClass::LessEq, Box::new(BinOpParselet {}));
+        infix_parselets.insert(TokenClass::GreaterEq, Box::new(BinOpParselet {}));
+        infix_parselets.insert(TokenClass::And, Box::new(BinOpParselet {}));
+        infix_parselets.insert(TokenClass::Or, Box::new(BinOpParselet {}));
         infix_parselets.insert(TokenClass::OpenParen, Box::new(FuncParselet {}));
+        infix_parselets.insert(TokenClass::OpenBracket, Box::new(IndexParselet {}));
         infix_parselets.insert(TokenClass::Prime, Box::new(PrimeParselet {}));
+        infix_parselets.insert(TokenClass::Arrow, Box::new(LambdaParselet {}));
+        infix_parselets.insert(TokenClass::Colon, Box::new(RangeParselet {}));
 
         Self {
             prefix_parselets,
@@ -90,33 +187,77 @@ impl Parser {
     }
 
     /// Parse code into an expression.
-    pub fn parse(&self, tokenizer: &mut Tokenizer, precedence: u8) -> Expression {
+    pub fn parse(&self, tokenizer: &mut Tokenizer, precedence: u8) -> Result<Expression, Error> {
         let token = match tokenizer.next() {
             Some(t) => t,
-            None => return Expression::Nil,
+            None => return Err(UnexpectedEof.with_span(tokenizer.eof_span())),
         };
 
-        let parselet: &Box<dyn PrefixParselet> = match self.prefix_parselets.get(&token.get_class()) {
-            Some(p) => p,
-            None => return Expression::Nil,
+        let parselet: &dyn PrefixParselet = match self.prefix_parselets.get(&token.get_class()) {
+            Some(p) => p.as_ref(),
+            None => return Err(UnexpectedEof.with_span(token.get_span())),
         };
-        let mut left = parselet.parse(self, tokenizer, token);
+        let mut left = parselet.parse(self, tokenizer, token)?;
 
         while precedence < tokenizer.get_next_precedence() {
+            // Inside a matrix, a sign like the one in `[1 -2]` starts the next value,
+            // as does a parenthesis or bracket like the one in `[x (2)]`
+            if tokenizer.in_matrix() && tokenizer.next_starts_value() {
+                break;
+            }
+
             let next = match tokenizer.peek() {
                 Some(t) => t,
                 None => break,
             };
             
-            let parselet: &Box<dyn InfixParselet> = match self.infix_parselets.get(&next.get_class()) {
-                Some(p) => p,
+            let parselet: &dyn InfixParselet = match self.infix_parselets.get(&next.get_class()) {
+                Some(p) => p.as_ref(),
                 None => break,
             };
             tokenizer.next();
 
-            left = parselet.parse(self, tokenizer, next, left);
+            left = parselet.parse(self, tokenizer, next, left)?;
         }
 
-        left
+        Ok(left)
+    }
+
+    /// Parse the next statement of a program, including the semicolon or newline
+    /// that ends it.  Returns `None` at the end of the program.
+    /// 
+    /// Empty statements (such as blank lines) are skipped.
+    pub fn parse_statement(&self, tokenizer: &mut Tokenizer) -> Result<Option<Statement>, Error> {
+        while tokenizer.peek().is_some_and(|t| t.check(TokenClass::Newline) || t.check(TokenClass::Semicolon)) {
+            tokenizer.next();
+        }
+
+        let start = match tokenizer.peek() {
+            Some(t) => t.get_span(),
+            None => return Ok(None),
+        };
+
+        let expression = self.parse(tokenizer, 0)?;
+        let span = start.to(tokenizer.last_span());
+
+        // Each statement must end with a semicolon, a newline, or the end of the program
+        let is_silent = match tokenizer.next() {
+            Some(t) if t.check(TokenClass::Semicolon) => true,
+            Some(t) if t.check(TokenClass::Newline) => false,
+            Some(t) => return Err(UnexpectedEof.with_span(t.get_span())),
+            None => false,
+        };
+
+        Ok(Some (Statement {
+            expression,
+            span,
+            is_silent,
+        }))
+    }
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Self::new()
     }
 }
\ No newline at end of file
@@ -1,39 +1,94 @@
 //! A parselet for parentheticals.
+//! 
+//! A parenthesized list of parameters followed by an arrow, such as
+//! `(x, y) -> x*y`, introduces a lambda.
 
 use crate::parselet_utils::*;
+use crate::parser::UnitParselet;
 use crate::error::*;
 
+use super::lambda_parselet::LambdaParselet;
+
 pub struct ParenParselet;
 
-impl PrefixParselet for ParenParselet {
-    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, _token: Token) -> Expression {
+impl ParenParselet {
+    /// Parses the remainder of a parameter list, given the parameters parsed so far.
+    fn parse_params(tokenizer: &mut Tokenizer, mut params: Vec<String>) -> Result<Vec<String>, Error> {
+        loop {
+            let next = match tokenizer.next() {
+                Some(t) => t,
+                None => return Err(ExpectedCloseParen.with_span(tokenizer.eof_span())),
+            };
+
+            match next.get_class() {
+                TokenClass::CloseParen => break,
+                TokenClass::Comma => (),
+                _ => return Err(ExpectedCloseParen.with_span(next.get_span())),
+            }
+
+            match tokenizer.next() {
+                Some(t) if t.get_class() == TokenClass::Identifier => params.push(t.get_value()),
+                Some(t) => return Err(ExpectedIdentifier.with_span(t.get_span())),
+                None => return Err(ExpectedIdentifier.with_span(tokenizer.eof_span())),
+            }
+        }
+
+        // The parameter list must be followed by an arrow
+        match tokenizer.next() {
+            Some(t) if t.get_class() == TokenClass::Arrow => (),
+            Some(t) => return Err(UnexpectedEof.with_span(t.get_span())),
+            None => return Err(UnexpectedEof.with_span(tokenizer.eof_span())),
+        }
+
+        Ok(params)
+    }
+
+    /// Parses the contents of a parenthetical, up to and including the closing parenthesis.
+    fn parse_contents(parser: &Parser, tokenizer: &mut Tokenizer, token: Token) -> Result<Expression, Error> {
         // Get the expression inside the parenthetical
         let next = match tokenizer.peek() {
             Some(t) => t,
-            None => {
-                throw(UnexpectedEof);
-                return Expression::Nil;
-            },
+            None => return Err(UnexpectedEof.with_span(tokenizer.eof_span())),
         };
 
-        let expr = parser.parse(tokenizer, next.get_class().into());
+        // Lambdas of no parameters look like `() -> ...`
+        if next.get_class() == TokenClass::CloseParen {
+            let params = Self::parse_params(tokenizer, Vec::new())?;
+            return LambdaParselet::parse_body(parser, tokenizer, params);
+        }
+
+        let expr = parser.parse(tokenizer, 0)?;
 
         let next = match tokenizer.peek() {
             Some(t) => t,
-            None => {
-                throw(UnexpectedEof);
-                return Expression::Nil;
-            },
+            None => return Err(ExpectedCloseParen.with_span(token.get_span().to(tokenizer.eof_span()))),
         };
 
         if next.get_class() == TokenClass::CloseParen {
             // Consume the parenthesis
             tokenizer.next();
             
-            expr
+            Ok(expr)
+        } else if next.get_class() == TokenClass::Comma {
+            // This is a list of lambda parameters
+            if let Expression::Identifier (s) = expr {
+                let params = Self::parse_params(tokenizer, vec![s])?;
+                LambdaParselet::parse_body(parser, tokenizer, params)
+            } else {
+                Err(ExpectedIdentifier.with_span(next.get_span()))
+            }
         } else {
-            throw(ExpectedCloseParen);
-            return Expression::Nil;
+            Err(ExpectedCloseParen.with_span(next.get_span()))
         }
     }
-}
\ No newline at end of file
+}
+
+impl PrefixParselet for ParenParselet {
+    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, token: Token) -> Result<Expression, Error> {
+        tokenizer.open(TokenClass::OpenParen);
+        let result = Self::parse_contents(parser, tokenizer, token);
+        tokenizer.close();
+
+        UnitParselet::parse_brackets(tokenizer, result?)
+    }
+}
@@ -1,15 +1,17 @@
 //! A parselet for transpose symbols (`'`).
 
 use crate::parselet_utils::*;
+use crate::error::*;
 
 pub struct PrimeParselet;
 
 impl InfixParselet for PrimeParselet {
-    fn parse(&self, _parser: &Parser, _tokenizer: &mut Tokenizer, _token: Token, left: Expression) -> Expression {
+    fn parse(&self, _parser: &Parser, _tokenizer: &mut Tokenizer, token: Token, left: Expression) -> Result<Expression, Error> {
         // Call the transpose function
-        Expression::Call {
+        Ok(Expression::Call {
             name: "t".to_string(),
             args: vec![left],
-        }
+            span: token.get_span(),
+        })
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,35 @@
+//! A parselet for ranges such as `1:5` and `0:0.25:1`.
+
+use crate::parselet_utils::*;
+use crate::error::*;
+
+pub struct RangeParselet;
+
+impl InfixParselet for RangeParselet {
+    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, token: Token, left: Expression) -> Result<Expression, Error> {
+        // Ranges do not chain, so this stops at a second colon
+        let precedence: u8 = token.get_class().into();
+        let right = parser.parse(tokenizer, precedence)?;
+
+        // A second colon means the middle value was the step, as in `start:step:end`
+        match tokenizer.peek() {
+            Some(t) if t.check(TokenClass::Colon) => {
+                tokenizer.next();
+                let end = parser.parse(tokenizer, precedence)?;
+
+                Ok(Expression::Range {
+                    start: Box::new(left),
+                    step: Some (Box::new(right)),
+                    end: Box::new(end),
+                    span: token.get_span().to(t.get_span()),
+                })
+            },
+            _ => Ok(Expression::Range {
+                start: Box::new(left),
+                step: None,
+                end: Box::new(right),
+                span: token.get_span(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+//! A parselet for unary operations (`-x`, `+x` and `!x`).
+
+use crate::parselet_utils::*;
+use crate::error::*;
+
+pub struct UnaryParselet;
+
+impl PrefixParselet for UnaryParselet {
+    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, token: Token) -> Result<Expression, Error> {
+        // Unary operators bind as tightly as multiplication, but more loosely
+        // than exponentiation, so `-x^2` is `-(x^2)`
+        let operand = parser.parse(tokenizer, TokenClass::Multiply.into())?;
+
+        Ok(Expression::UnaryOp {
+            op: token.get_value(),
+            operand: Box::new(operand),
+            span: token.get_span(),
+        })
+    }
+}
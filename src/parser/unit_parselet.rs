@@ -0,0 +1,132 @@
+//! A parselet for the units of values.
+
+use crate::parselet_utils::*;
+use crate::error::*;
+use crate::Unit;
+
+pub struct UnitParselet;
+
+impl UnitParselet {
+    /// Attaches units to a literal, if it is followed by a unit (as in `3 m`
+    /// or `2 s^-1`) or by units in brackets (as in `5 [kN]` or `9.81 [m/s^2]`).
+    ///
+    /// Inside a matrix, values are never followed by units, so `[1 m]` is
+    /// a matrix of two values.
+    pub fn parse_annotation(tokenizer: &mut Tokenizer, value: Expression) -> Result<Expression, Error> {
+        if tokenizer.in_matrix() {
+            return Ok(value);
+        }
+
+        let is_unit = tokenizer.peek().is_some_and(|t| t.check(TokenClass::Identifier) && Unit::parse(&t.get_value()).is_some());
+        if !is_unit {
+            return Self::parse_brackets(tokenizer, value);
+        }
+
+        Ok(Expression::Quantity {
+            value: Box::new(value),
+            unit: Self::parse_factor(tokenizer)?,
+        })
+    }
+
+    /// Attaches units in brackets to a number or a parenthesized value, as in
+    /// `5 [m]` or `(2 ± 0.1) [m]`.
+    ///
+    /// Numbers cannot be indexed, so a bracket after a number always holds
+    /// units.  After a closing parenthesis, only a separated bracket holds
+    /// units, so `(0:3)[2]` is an index instead.  A bracket after an
+    /// identifier is always an index, so `A [2]` is `A[2]`.
+    pub fn parse_brackets(tokenizer: &mut Tokenizer, value: Expression) -> Result<Expression, Error> {
+        let is_bracket = tokenizer.peek().is_some_and(|t| t.check(TokenClass::OpenBracket));
+        let is_number = matches!(value, Expression::Int (_) | Expression::Float (_) | Expression::Rational (_) | Expression::Complex (_));
+        let is_paren = tokenizer.lookbehind(1).is_some_and(|t| t.check(TokenClass::CloseParen));
+        let is_separated = tokenizer.peek().is_some_and(|t| {
+            let (previous, span) = (tokenizer.last_span(), t.get_span());
+            (previous.end_line(), previous.end_col()) != (span.line(), span.col())
+        });
+        if tokenizer.in_matrix() || !is_bracket || !(is_number || (is_paren && is_separated)) {
+            return Ok(value);
+        }
+
+        tokenizer.next();
+        let unit = Self::parse_unit(tokenizer)?;
+
+        match tokenizer.next() {
+            Some(t) if t.check(TokenClass::CloseBracket) => (),
+            Some(t) => return Err(ExpectedCloseBracket.with_span(t.get_span())),
+            None => return Err(ExpectedCloseBracket.with_span(tokenizer.eof_span())),
+        }
+
+        Ok(Expression::Quantity {
+            value: Box::new(value),
+            unit,
+        })
+    }
+
+    /// Parses a product or quotient of units, like `kg*m/s^2` or `kN m`.
+    fn parse_unit(tokenizer: &mut Tokenizer) -> Result<Unit, Error> {
+        let mut unit = Self::parse_factor(tokenizer)?;
+
+        while let Some(current) = tokenizer.peek() {
+            match current.get_class() {
+                TokenClass::Multiply => {
+                    tokenizer.next();
+                    unit = unit.multiply(&Self::parse_factor(tokenizer)?);
+                },
+                TokenClass::Divide => {
+                    tokenizer.next();
+                    unit = unit.divide(&Self::parse_factor(tokenizer)?);
+                },
+                TokenClass::Identifier => unit = unit.multiply(&Self::parse_factor(tokenizer)?),
+                _ => break,
+            }
+        }
+
+        Ok(unit)
+    }
+
+    /// Parses a single unit with an optional integer power, like `s^-1`.
+    ///
+    /// The number `1` is also a unit, so that `1/s` can be written.
+    fn parse_factor(tokenizer: &mut Tokenizer) -> Result<Unit, Error> {
+        let current = match tokenizer.next() {
+            Some(t) => t,
+            None => return Err(ExpectedIdentifier.with_span(tokenizer.eof_span())),
+        };
+
+        let unit = match current.get_class() {
+            TokenClass::Identifier => match Unit::parse(&current.get_value()) {
+                Some(u) => u,
+                None => return Err(UnknownUnit (current.get_value()).with_span(current.get_span())),
+            },
+            TokenClass::Int if current.get_value() == "1" => Unit::none(),
+            _ => return Err(ExpectedIdentifier.with_span(current.get_span())),
+        };
+
+        // Only integer powers belong to the unit, so `2 m^x` is `(2 m)^x`
+        let is_int = |t: Option<Token>| t.is_some_and(|t| t.check(TokenClass::Int));
+        let is_sign = |t: Option<Token>| t.is_some_and(|t| t.check(TokenClass::Minus) || t.check(TokenClass::Plus));
+        let is_power = tokenizer.peek().is_some_and(|t| t.check(TokenClass::Caret)) && (
+            is_int(tokenizer.lookahead(1))
+            || (is_sign(tokenizer.lookahead(1)) && is_int(tokenizer.lookahead(2)))
+        );
+        if !is_power {
+            return Ok(unit);
+        }
+
+        tokenizer.next();
+        let negative = tokenizer.peek().is_some_and(|t| t.check(TokenClass::Minus));
+        if is_sign(tokenizer.peek()) {
+            tokenizer.next();
+        }
+
+        let exponent = match tokenizer.next() {
+            Some(t) => match str::parse::<i32>(&t.get_value()) {
+                Ok(n) => n,
+                Err(_) => return Err(CouldNotParseNumeric.with_span(t.get_span())),
+            },
+            None => return Err(UnexpectedEof.with_span(tokenizer.eof_span())),
+        };
+
+        Ok(unit.pow(if negative { -exponent } else { exponent }))
+    }
+}
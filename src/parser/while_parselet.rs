@@ -0,0 +1,21 @@
+//! A parselet for loops such as `while x > 1 { x = x/2 }`.
+
+use crate::parselet_utils::*;
+use crate::error::*;
+
+use super::block_parselet::BlockParselet;
+
+pub struct WhileParselet;
+
+impl PrefixParselet for WhileParselet {
+    fn parse(&self, parser: &Parser, tokenizer: &mut Tokenizer, token: Token) -> Result<Expression, Error> {
+        let condition = parser.parse(tokenizer, 0)?;
+        let body = BlockParselet::parse_block(parser, tokenizer)?;
+
+        Ok(Expression::While {
+            condition: Box::new(condition),
+            body: Box::new(body),
+            span: token.get_span(),
+        })
+    }
+}
@@ -0,0 +1,63 @@
+//! Computes absolute values (magnitudes).
+
+use num_complex::{
+    Complex64,
+    ComplexFloat,
+};
+
+use crate::{
+    Matrix,
+    Uncertain,
+};
+use crate::error::*;
+
+use super::StdFunc;
+
+#[derive(Clone)]
+pub struct Abs;
+
+impl Abs {
+    /// Evaluates `Abs` while minimizing heap allocation.
+    pub fn evalpure<T: ComplexFloat<Real = f64>>(matrix: &Matrix<T>) -> Matrix<f64> {
+        let mut output = Vec::new();
+        let rows = matrix.rows();
+        let cols = matrix.cols();
+
+        for v in matrix.vals() {
+            output.push(v.abs());
+        }
+
+        Matrix::new(
+            rows,
+            cols,
+            output,
+        )
+    }
+}
+
+impl StdFunc for Abs {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Self::evalpure(&args[0]))
+    }
+
+    fn eval_uncertain(&self, args: Vec<Matrix<Uncertain>>) -> Result<Matrix<Uncertain>, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        let vals = args[0].vals().iter().map(|v| v.abs()).collect::<Vec<Uncertain>>();
+        Ok(Matrix::new(args[0].rows(), args[0].cols(), vals))
+    }
+
+    fn eval_complex(&self, args: Vec<Matrix<Complex64>>) -> Result<Matrix<Complex64>, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Self::evalpure(&args[0]).to_complex())
+    }
+}
\ No newline at end of file
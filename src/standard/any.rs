@@ -0,0 +1,39 @@
+//! Checks whether any value of a matrix is true (nonzero).
+
+use std::collections::HashMap;
+
+use crate::{
+    Matrix,
+    Expression,
+};
+use crate::error::*;
+
+use super::StdFunc;
+
+#[derive(Clone)]
+pub struct Any;
+
+impl Any {
+    /// Evaluates `Any` while minimizing heap allocation.
+    pub fn evalpure(matrix: &Matrix) -> bool {
+        matrix.vals().iter().any(|v| *v != 0.0)
+    }
+}
+
+impl StdFunc for Any {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Matrix::new(1, 1, vec![Self::evalpure(&args[0]) as i64 as f64]))
+    }
+
+    fn call(&self, args: Vec<Expression>, _variables: &mut HashMap<String, Expression>) -> Result<Expression, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Expression::Bool (Self::evalpure(&args[0].to_matrix()?)))
+    }
+}
@@ -0,0 +1,95 @@
+//! Computes Cholesky factorizations.
+
+use crate::Matrix;
+use crate::error::*;
+
+use super::StdFunc;
+
+/// Computes the Cholesky factorization `A = LL'` of a symmetric positive
+/// definite matrix, where `L` is lower triangular.
+///
+/// `chol(A)` is `L`, and is an error for any matrix that is not symmetric
+/// positive definite.
+#[derive(Clone)]
+pub struct Cholesky;
+
+impl Cholesky {
+    /// Factorizes a symmetric positive definite matrix, returning `L`.
+    pub fn factorize(matrix: &Matrix) -> Result<Matrix, Error> {
+        if matrix.rows() != matrix.cols() {
+            return Err(SquareMatrixRequired);
+        }
+
+        let n = matrix.rows();
+        let mut lower = Matrix::new(n, n, vec![0.0; n*n]);
+
+        for j in 0..n {
+            for i in j..n {
+                if matrix[[i, j]] != matrix[[j, i]] {
+                    return Err(NotPositiveDefinite);
+                }
+
+                let mut sum = matrix[[i, j]];
+                for k in 0..j {
+                    sum -= lower[[i, k]]*lower[[j, k]];
+                }
+
+                if i == j {
+                    // Only positive definite matrices have positive pivots
+                    if sum <= 0.0 || !sum.is_finite() {
+                        return Err(NotPositiveDefinite);
+                    }
+                    lower[[j, j]] = sum.sqrt();
+                } else {
+                    lower[[i, j]] = sum/lower[[j, j]];
+                }
+            }
+        }
+
+        Ok(lower)
+    }
+
+    /// Solves `LL'X = B` for `X` by forward and back substitution, given `L`.
+    pub fn solve(lower: &Matrix, b: &Matrix) -> Result<Matrix, Error> {
+        let n = lower.rows();
+
+        if b.rows() != n {
+            return Err(ImproperDimensions);
+        }
+
+        let mut output = Matrix::new(n, b.cols(), vec![0.0; n*b.cols()]);
+
+        for k in 0..b.cols() {
+            // Solve `Ly = b`
+            let mut y = vec![0.0; n];
+            for i in 0..n {
+                let mut sum = b[[i, k]];
+                for j in 0..i {
+                    sum -= lower[[i, j]]*y[j];
+                }
+                y[i] = sum/lower[[i, i]];
+            }
+
+            // Solve `L'x = y`
+            for i in (0..n).rev() {
+                let mut sum = y[i];
+                for j in i + 1..n {
+                    sum -= lower[[j, i]]*output[[j, k]];
+                }
+                output[[i, k]] = sum/lower[[i, i]];
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+impl StdFunc for Cholesky {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Self::factorize(&args[0])
+    }
+}
\ No newline at end of file
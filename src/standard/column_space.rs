@@ -0,0 +1,45 @@
+//! Computes column spaces.
+
+use crate::Matrix;
+use crate::error::*;
+
+use super::{
+    StdFunc,
+    SingularValues,
+};
+
+/// Computes an orthonormal basis for the column space (range) of a matrix,
+/// as the columns of a matrix.
+#[derive(Clone)]
+pub struct ColumnSpace;
+
+impl ColumnSpace {
+    /// Evaluates `ColumnSpace` while minimizing heap allocation.
+    pub fn evalpure(matrix: &Matrix) -> Result<Matrix, Error> {
+        let m = matrix.rows();
+        let (u, s, _) = SingularValues::decompose(matrix)?;
+        let tolerance = SingularValues::tolerance(matrix, &s);
+
+        // The left singular vectors of nonzero singular values span the column space
+        let columns = (0..s.len()).filter(|k| s[*k] > tolerance).collect::<Vec<usize>>();
+
+        let mut output = Vec::new();
+        for i in 0..m {
+            for k in &columns {
+                output.push(u[[i, *k]]);
+            }
+        }
+
+        Ok(Matrix::new(m, columns.len(), output))
+    }
+}
+
+impl StdFunc for ColumnSpace {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Self::evalpure(&args[0])
+    }
+}
\ No newline at end of file
@@ -0,0 +1,39 @@
+//! Computes condition numbers.
+
+use crate::Matrix;
+use crate::error::*;
+
+use super::{
+    StdFunc,
+    SingularValues,
+};
+
+/// Computes the condition number of a matrix in the 2-norm, the ratio of
+/// its largest and smallest singular values.
+/// 
+/// Singular matrices have an infinite condition number.
+#[derive(Clone)]
+pub struct ConditionNumber;
+
+impl ConditionNumber {
+    /// Evaluates `ConditionNumber` while minimizing heap allocation.
+    pub fn evalpure(matrix: &Matrix) -> Result<f64, Error> {
+        let s = SingularValues::evalpure(matrix)?;
+
+        match (s.vals().first(), s.vals().last()) {
+            (Some(largest), Some(smallest)) if *smallest > 0.0 => Ok(largest/smallest),
+            (Some(_), Some(_)) => Ok(f64::INFINITY),
+            _ => Ok(0.0),
+        }
+    }
+}
+
+impl StdFunc for ConditionNumber {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Matrix::new(1, 1, vec![Self::evalpure(&args[0])?]))
+    }
+}
\ No newline at end of file
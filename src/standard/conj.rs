@@ -0,0 +1,51 @@
+//! Computes complex conjugates.
+
+use num_complex::{
+    Complex64,
+    ComplexFloat,
+};
+
+use crate::Matrix;
+use crate::error::*;
+
+use super::StdFunc;
+
+#[derive(Clone)]
+pub struct Conj;
+
+impl Conj {
+    /// Evaluates `Conj` while minimizing heap allocation.
+    pub fn evalpure<T: ComplexFloat>(matrix: &Matrix<T>) -> Matrix<T> {
+        let mut output = Vec::new();
+        let rows = matrix.rows();
+        let cols = matrix.cols();
+
+        for v in matrix.vals() {
+            output.push(v.conj());
+        }
+
+        Matrix::new(
+            rows,
+            cols,
+            output,
+        )
+    }
+}
+
+impl StdFunc for Conj {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Self::evalpure(&args[0]))
+    }
+
+    fn eval_complex(&self, args: Vec<Matrix<Complex64>>) -> Result<Matrix<Complex64>, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Self::evalpure(&args[0]))
+    }
+}
\ No newline at end of file
@@ -1,6 +1,14 @@
 //! Computes cosines.
 
-use crate::Matrix;
+use num_complex::{
+    Complex64,
+    ComplexFloat,
+};
+
+use crate::{
+    Matrix,
+    Uncertain,
+};
 use crate::error::*;
 
 use super::StdFunc;
@@ -10,7 +18,7 @@ pub struct Cos;
 
 impl Cos {
     /// Evaluates `Cos` while minimizing heap allocation.
-    pub fn evalpure(matrix: &Matrix) -> Matrix {
+    pub fn evalpure<T: ComplexFloat>(matrix: &Matrix<T>) -> Matrix<T> {
         let mut output = Vec::new();
         let rows = matrix.rows();
         let cols = matrix.cols();
@@ -28,12 +36,28 @@ impl Cos {
 }
 
 impl StdFunc for Cos {
-    fn eval(&self, args: Vec<Matrix>) -> Matrix {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
         if args.len() != 1 {
-            throw(WrongNumberOfArgs);
-            return Matrix::new(0, 0, Vec::new());
+            return Err(WrongNumberOfArgs);
         }
 
-        Self::evalpure(&args[0])
+        Ok(Self::evalpure(&args[0]))
+    }
+
+    fn eval_uncertain(&self, args: Vec<Matrix<Uncertain>>) -> Result<Matrix<Uncertain>, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        let vals = args[0].vals().iter().map(|v| v.cos()).collect::<Vec<Uncertain>>();
+        Ok(Matrix::new(args[0].rows(), args[0].cols(), vals))
+    }
+
+    fn eval_complex(&self, args: Vec<Matrix<Complex64>>) -> Result<Matrix<Complex64>, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Self::evalpure(&args[0]))
     }
 }
\ No newline at end of file
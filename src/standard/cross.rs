@@ -1,6 +1,11 @@
 //! Defines cross products on vectors.
 
-use crate::Matrix;
+use num_traits::Num;
+
+use crate::{
+    Matrix,
+    Uncertain,
+};
 use crate::error::*;
 
 use super::StdFunc;
@@ -10,7 +15,7 @@ pub struct Cross;
 
 impl Cross {
     /// Evaluates `Cross` while minimizing heap allocation.
-    pub fn evalpure(vec1: &Matrix, vec2: &Matrix) -> Matrix {
+    pub fn evalpure<T: Clone + Num>(vec1: &Matrix<T>, vec2: &Matrix<T>) -> Result<Matrix<T>, Error> {
         let a = vec1.vals();
         let b = vec2.vals();
 
@@ -19,25 +24,31 @@ impl Cross {
             || vec1.rows() != vec2.rows()
             || vec1.cols() != vec2.cols()
         {
-            throw(ImproperDimensions);
-            return Matrix::new(0, 0, Vec::new());
+            return Err(ImproperDimensions);
         }
 
         let outputvec = vec![
-            a[1]*b[2] - a[2]*b[1],
-            a[2]*b[0] - a[0]*b[2],
-            a[0]*b[1] - a[1]*b[0],
+            a[1].clone()*b[2].clone() - a[2].clone()*b[1].clone(),
+            a[2].clone()*b[0].clone() - a[0].clone()*b[2].clone(),
+            a[0].clone()*b[1].clone() - a[1].clone()*b[0].clone(),
         ];
 
-        Matrix::new(vec1.rows(), vec1.cols(), outputvec)
+        Ok(Matrix::new(vec1.rows(), vec1.cols(), outputvec))
     }
 }
 
 impl StdFunc for Cross {
-    fn eval(&self, args: Vec<Matrix>) -> Matrix {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 2 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Self::evalpure(&args[0], &args[1])
+    }
+
+    fn eval_uncertain(&self, args: Vec<Matrix<Uncertain>>) -> Result<Matrix<Uncertain>, Error> {
         if args.len() != 2 {
-            throw(WrongNumberOfArgs);
-            return Matrix::new(0, 0, Vec::new());
+            return Err(WrongNumberOfArgs);
         }
 
         Self::evalpure(&args[0], &args[1])
@@ -1,62 +1,60 @@
 //! Computes matrix determinants.
 
-use crate::Matrix;
+use num_complex::Complex64;
+use num_rational::BigRational;
+
+use crate::{
+    Matrix,
+    Uncertain,
+};
 use crate::error::*;
 
 use super::{
     StdFunc,
-    GetMinors,   
+    Lu,
+    Pivot,
 };
 
 #[derive(Clone)]
 pub struct Determinant;
 
 impl Determinant {
-    pub fn evalpure(matrix: &Matrix) -> f64 {
-        if matrix.rows() != matrix.cols() {
-            // Square matrices only
-            throw(SquareMatrixRequired);
-            return 0.0;
-        }
-    
-        let dim = matrix.rows();
-    
-        let det = if dim == 1 {
-            matrix[[0, 0]]
-        } else if dim == 2 {
-            matrix[[0, 0]] * matrix[[1, 1]] - matrix[[1, 0]] * matrix[[0, 1]]
-        } else {
-            let mut d = 0.0f64;
-    
-            let mut sign = 1.0;
-    
-            // Laplace-expand the matrix down the first column
-            for i in 0..dim {
-                let minors = GetMinors::evalpure(&matrix, i, 0);
-    
-                d += matrix[[i, 0]] * Determinant::evalpure(&minors) * sign;
-    
-                if sign == 1.0 {
-                    sign = -1.0;
-                } else {
-                    sign = 1.0;
-                }
-            }
-    
-            d
-        };
-
-        det
+    /// Evaluates `Determinant` from the LU factorization of the matrix.
+    pub fn evalpure<T: Pivot>(matrix: &Matrix<T>) -> Result<T, Error> {
+        Ok(Lu::factorize(matrix)?.determinant())
     }
 }
 
 impl StdFunc for Determinant {
-    fn eval(&self, args: Vec<Matrix>) -> Matrix {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Matrix::new(1, 1, vec![Self::evalpure(&args[0])?]))
+    }
+
+    fn eval_complex(&self, args: Vec<Matrix<Complex64>>) -> Result<Matrix<Complex64>, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Matrix::new(1, 1, vec![Self::evalpure(&args[0])?]))
+    }
+
+    fn eval_uncertain(&self, args: Vec<Matrix<Uncertain>>) -> Result<Matrix<Uncertain>, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Matrix::new(1, 1, vec![Self::evalpure(&args[0])?]))
+    }
+
+    fn eval_rational(&self, args: Vec<Matrix<BigRational>>) -> Option<Result<Matrix<BigRational>, Error>> {
         if args.len() != 1 {
-            throw(WrongNumberOfArgs);
-            return Matrix::new(0, 0, Vec::new());
+            return Some(Err(WrongNumberOfArgs));
         }
 
-        Matrix::new(1, 1, vec![Self::evalpure(&args[0])])
+        Some(Self::evalpure(&args[0]).map(|d| Matrix::new(1, 1, vec![d])))
     }
 }
\ No newline at end of file
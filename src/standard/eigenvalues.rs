@@ -0,0 +1,425 @@
+//! Computes eigenvalues.
+
+use std::collections::HashMap;
+
+use num_complex::Complex64;
+
+use crate::{
+    Matrix,
+    Expression,
+};
+use crate::error::*;
+
+use super::{
+    StdFunc,
+    Eigenvectors,
+};
+
+/// Largest number of sweeps of the Jacobi method, or of iterations of the QR
+/// algorithm for each eigenvalue, before giving up.
+const MAX_ITERATIONS: usize = 60;
+
+/// Computes the eigenvalues of a real square matrix, as a column vector.
+///
+/// Symmetric matrices have real eigenvalues, which are sorted in ascending order.
+/// Other matrices may have complex eigenvalues, in conjugate pairs, which are
+/// sorted by real part and then by imaginary part.
+///
+/// `[V, D] = eig(A)` assigns the eigenvectors, as from `eigvec`, and the
+/// eigenvalues on the diagonal of a matrix, so that `AV = VD`.
+#[derive(Clone)]
+pub struct Eigenvalues;
+
+impl Eigenvalues {
+    /// Evaluates `Eigenvalues` while minimizing heap allocation.
+    pub fn evalpure(matrix: &Matrix) -> Result<Matrix<Complex64>, Error> {
+        let values = if Self::is_symmetric(matrix)? {
+            Self::symmetric(matrix)?.0.into_iter().map(|x| Complex64::new(x, 0.0)).collect()
+        } else {
+            Self::general(matrix)?
+        };
+
+        Ok(Matrix::new(values.len(), 1, values))
+    }
+
+    /// Checks whether or not a square matrix is symmetric.
+    pub fn is_symmetric(matrix: &Matrix) -> Result<bool, Error> {
+        if matrix.rows() != matrix.cols() {
+            return Err(SquareMatrixRequired);
+        }
+
+        let n = matrix.rows();
+        Ok((0..n).all(|i| (0..i).all(|j| matrix[[i, j]] == matrix[[j, i]])))
+    }
+
+    /// Finds the eigenvalues and (orthonormal) eigenvectors of a symmetric matrix
+    /// by the cyclic Jacobi method, which rotates away each off-diagonal value in turn.
+    ///
+    /// The eigenvectors are the columns of the returned matrix.
+    pub fn symmetric(matrix: &Matrix) -> Result<(Vec<f64>, Matrix), Error> {
+        let n = matrix.rows();
+        let mut a = matrix.clone();
+        let mut v = Matrix::new(n, n, vec![0.0; n*n]);
+        for i in 0..n {
+            v[[i, i]] = 1.0;
+        }
+
+        let norm = matrix.vals().iter().map(|x| x*x).sum::<f64>().sqrt();
+        let mut converged = false;
+
+        for _ in 0..MAX_ITERATIONS {
+            let off = (0..n).map(|i| (0..i).map(|j| a[[i, j]]*a[[i, j]]).sum::<f64>()).sum::<f64>().sqrt();
+            if off <= f64::EPSILON*norm {
+                converged = true;
+                break;
+            }
+
+            for p in 0..n {
+                for q in p + 1..n {
+                    if a[[p, q]] == 0.0 {
+                        continue;
+                    }
+
+                    // Choose the smaller rotation that zeroes `a[p, q]`
+                    let theta = (a[[q, q]] - a[[p, p]])/(2.0*a[[p, q]]);
+                    let t = if theta == 0.0 {
+                        1.0
+                    } else {
+                        theta.signum()/(theta.abs() + (theta*theta + 1.0).sqrt())
+                    };
+                    let c = 1.0/(t*t + 1.0).sqrt();
+                    let s = t*c;
+
+                    for k in 0..n {
+                        let (x, y) = (a[[k, p]], a[[k, q]]);
+                        a[[k, p]] = c*x - s*y;
+                        a[[k, q]] = s*x + c*y;
+                    }
+                    for k in 0..n {
+                        let (x, y) = (a[[p, k]], a[[q, k]]);
+                        a[[p, k]] = c*x - s*y;
+                        a[[q, k]] = s*x + c*y;
+                    }
+                    for k in 0..n {
+                        let (x, y) = (v[[k, p]], v[[k, q]]);
+                        v[[k, p]] = c*x - s*y;
+                        v[[k, q]] = s*x + c*y;
+                    }
+                }
+            }
+        }
+
+        if !converged {
+            return Err(DidNotConverge);
+        }
+
+        // Sort the eigenvalues, along with their eigenvectors
+        let mut order = (0..n).collect::<Vec<usize>>();
+        order.sort_by(|&i, &j| a[[i, i]].total_cmp(&a[[j, j]]));
+
+        let values = order.iter().map(|&i| a[[i, i]]).collect::<Vec<f64>>();
+        let mut vectors = Matrix::new(n, n, vec![0.0; n*n]);
+        for (j, &k) in order.iter().enumerate() {
+            // Make the largest component of each eigenvector positive
+            let largest = (0..n).map(|i| v[[i, k]]).fold(0.0, |m: f64, x| if x.abs() > m.abs() { x } else { m });
+            for i in 0..n {
+                vectors[[i, j]] = v[[i, k]]*largest.signum();
+            }
+        }
+
+        Ok((values, vectors))
+    }
+
+    /// Finds the eigenvalues of a general real matrix by reducing it to upper
+    /// Hessenberg form, then applying the shifted QR algorithm.
+    pub fn general(matrix: &Matrix) -> Result<Vec<Complex64>, Error> {
+        let mut values = Self::hqr(Self::hessenberg(matrix))?;
+
+        values.sort_by(|x, y| x.re.total_cmp(&y.re).then(x.im.total_cmp(&y.im)));
+
+        Ok(values)
+    }
+
+    /// Reduces a square matrix to upper Hessenberg form (with zeros below the
+    /// first subdiagonal) by Householder reflections, which keep its eigenvalues.
+    fn hessenberg(matrix: &Matrix) -> Matrix {
+        let n = matrix.rows();
+        let mut h = matrix.clone();
+        let mut ort = vec![0.0; n];
+
+        for m in 1..n.saturating_sub(1) {
+            let scale = (m..n).map(|i| h[[i, m - 1]].abs()).sum::<f64>();
+            if scale == 0.0 {
+                continue;
+            }
+
+            // Compute the Householder reflection that zeroes column `m - 1` below the subdiagonal
+            let mut sum = 0.0;
+            for i in (m..n).rev() {
+                ort[i] = h[[i, m - 1]]/scale;
+                sum += ort[i]*ort[i];
+            }
+            let g = if ort[m] > 0.0 { -sum.sqrt() } else { sum.sqrt() };
+            sum -= ort[m]*g;
+            ort[m] -= g;
+
+            // Apply the reflection from the left and from the right
+            for j in m..n {
+                let f = (m..n).rev().map(|i| ort[i]*h[[i, j]]).sum::<f64>()/sum;
+                for i in m..n {
+                    h[[i, j]] -= f*ort[i];
+                }
+            }
+            for i in 0..n {
+                let f = (m..n).rev().map(|j| ort[j]*h[[i, j]]).sum::<f64>()/sum;
+                for j in m..n {
+                    h[[i, j]] -= f*ort[j];
+                }
+            }
+
+            h[[m, m - 1]] = scale*g;
+            for i in m + 1..n {
+                h[[i, m - 1]] = 0.0;
+            }
+        }
+
+        h
+    }
+
+    /// Finds the eigenvalues of an upper Hessenberg matrix by the Francis
+    /// double-shift QR algorithm, deflating one or two eigenvalues at a time.
+    fn hqr(h: Matrix) -> Result<Vec<Complex64>, Error> {
+        let n = h.rows();
+
+        // Index from 1, which keeps the bounds of the algorithm simple
+        let mut a = Matrix::new(n + 1, n + 1, vec![0.0; (n + 1)*(n + 1)]);
+        for i in 1..=n {
+            for j in 1..=n {
+                a[[i, j]] = h[[i - 1, j - 1]];
+            }
+        }
+
+        let mut anorm = 0.0;
+        for i in 1..=n {
+            for j in (i - 1).max(1)..=n {
+                anorm += a[[i, j]].abs();
+            }
+        }
+
+        let mut wr = vec![0.0; n + 1];
+        let mut wi = vec![0.0; n + 1];
+        let mut nn = n;
+        let mut t = 0.0;
+        let (mut p, mut q, mut r): (f64, f64, f64);
+
+        while nn >= 1 {
+            let mut its = 0;
+            loop {
+                // Look for a single small subdiagonal value
+                let mut l = nn;
+                while l >= 2 {
+                    let mut s = a[[l - 1, l - 1]].abs() + a[[l, l]].abs();
+                    if s == 0.0 {
+                        s = anorm;
+                    }
+                    if a[[l, l - 1]].abs() + s == s {
+                        a[[l, l - 1]] = 0.0;
+                        break;
+                    }
+                    l -= 1;
+                }
+
+                let mut x = a[[nn, nn]];
+                if l == nn {
+                    // One root found
+                    wr[nn] = x + t;
+                    wi[nn] = 0.0;
+                    nn -= 1;
+                } else {
+                    let mut y = a[[nn - 1, nn - 1]];
+                    let mut w = a[[nn, nn - 1]]*a[[nn - 1, nn]];
+                    if l == nn - 1 {
+                        // Two roots found, which are either real or a conjugate pair
+                        p = 0.5*(y - x);
+                        q = p*p + w;
+                        let mut z = q.abs().sqrt();
+                        x += t;
+                        if q >= 0.0 {
+                            z = p + z.copysign(p);
+                            wr[nn - 1] = x + z;
+                            wr[nn] = x + z;
+                            if z != 0.0 {
+                                wr[nn] = x - w/z;
+                            }
+                            wi[nn - 1] = 0.0;
+                            wi[nn] = 0.0;
+                        } else {
+                            wr[nn - 1] = x + p;
+                            wr[nn] = x + p;
+                            wi[nn - 1] = -z;
+                            wi[nn] = z;
+                        }
+                        nn -= 2;
+                    } else {
+                        if its == MAX_ITERATIONS {
+                            return Err(DidNotConverge);
+                        }
+
+                        // Use an exceptional shift if convergence is slow
+                        if its == 10 || its == 20 {
+                            t += x;
+                            for i in 1..=nn {
+                                a[[i, i]] -= x;
+                            }
+                            let s = a[[nn, nn - 1]].abs() + a[[nn - 1, nn - 2]].abs();
+                            x = 0.75*s;
+                            y = x;
+                            w = -0.4375*s*s;
+                        }
+                        its += 1;
+
+                        // Look for two consecutive small subdiagonal values
+                        let mut m = nn - 2;
+                        loop {
+                            let z = a[[m, m]];
+                            r = x - z;
+                            let s = y - z;
+                            p = (r*s - w)/a[[m + 1, m]] + a[[m, m + 1]];
+                            q = a[[m + 1, m + 1]] - z - r - s;
+                            r = a[[m + 2, m + 1]];
+                            let s = p.abs() + q.abs() + r.abs();
+                            p /= s;
+                            q /= s;
+                            r /= s;
+                            if m == l {
+                                break;
+                            }
+                            let u = a[[m, m - 1]].abs()*(q.abs() + r.abs());
+                            let v = p.abs()*(a[[m - 1, m - 1]].abs() + z.abs() + a[[m + 1, m + 1]].abs());
+                            if u + v == v {
+                                break;
+                            }
+                            m -= 1;
+                        }
+
+                        for i in m + 2..=nn {
+                            a[[i, i - 2]] = 0.0;
+                            if i != m + 2 {
+                                a[[i, i - 3]] = 0.0;
+                            }
+                        }
+
+                        // Apply a double QR step to rows `l` to `nn` and columns `m` to `nn`
+                        for k in m..nn {
+                            if k != m {
+                                p = a[[k, k - 1]];
+                                q = a[[k + 1, k - 1]];
+                                r = if k != nn - 1 { a[[k + 2, k - 1]] } else { 0.0 };
+                                x = p.abs() + q.abs() + r.abs();
+                                if x != 0.0 {
+                                    p /= x;
+                                    q /= x;
+                                    r /= x;
+                                }
+                            }
+
+                            let s = (p*p + q*q + r*r).sqrt().copysign(p);
+                            if s == 0.0 {
+                                continue;
+                            }
+
+                            if k == m {
+                                if l != m {
+                                    a[[k, k - 1]] = -a[[k, k - 1]];
+                                }
+                            } else {
+                                a[[k, k - 1]] = -s*x;
+                            }
+                            p += s;
+                            x = p/s;
+                            y = q/s;
+                            let z = r/s;
+                            q /= p;
+                            r /= p;
+
+                            for j in k..=nn {
+                                p = a[[k, j]] + q*a[[k + 1, j]];
+                                if k != nn - 1 {
+                                    p += r*a[[k + 2, j]];
+                                    a[[k + 2, j]] -= p*z;
+                                }
+                                a[[k + 1, j]] -= p*y;
+                                a[[k, j]] -= p*x;
+                            }
+
+                            for i in l..=nn.min(k + 3) {
+                                p = x*a[[i, k]] + y*a[[i, k + 1]];
+                                if k != nn - 1 {
+                                    p += z*a[[i, k + 2]];
+                                    a[[i, k + 2]] -= p*r;
+                                }
+                                a[[i, k + 1]] -= p*q;
+                                a[[i, k]] -= p;
+                            }
+                        }
+                    }
+                }
+
+                if nn < 2 || l + 1 >= nn {
+                    break;
+                }
+            }
+        }
+
+        Ok((1..=n).map(|i| Complex64::new(wr[i], wi[i])).collect())
+    }
+}
+
+impl StdFunc for Eigenvalues {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        let values = Self::evalpure(&args[0])?;
+        if values.vals().iter().any(|z| z.im != 0.0) {
+            return Err(ComplexNotSupported);
+        }
+
+        Ok(Matrix::new(values.rows(), 1, values.vals().iter().map(|z| z.re).collect()))
+    }
+
+    /// Real matrices may have complex eigenvalues, so `eig([0 1; -1 0])` is `[-1i; 1i]`.
+    fn call(&self, args: Vec<Expression>, variables: &mut HashMap<String, Expression>) -> Result<Expression, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Expression::from(Self::evalpure(&args[0].to_matrix()?)?).simplify(variables)
+    }
+
+    fn call_multiple(&self, args: Vec<Expression>, count: usize, variables: &mut HashMap<String, Expression>) -> Result<Vec<Expression>, Error> {
+        match count {
+            1 => Ok(vec![self.call(args, variables)?]),
+            2 => {
+                if args.len() != 1 {
+                    return Err(WrongNumberOfArgs);
+                }
+
+                let matrix = args[0].to_matrix()?;
+                let values = Self::evalpure(&matrix)?;
+                let n = values.rows();
+                let mut diagonal = Matrix::new(n, n, vec![Complex64::new(0.0, 0.0); n*n]);
+                for i in 0..n {
+                    diagonal[[i, i]] = values[[i, 0]];
+                }
+
+                Ok(vec![
+                    Expression::from(Eigenvectors::evalpure(&matrix)?).simplify(variables)?,
+                    Expression::from(diagonal).simplify(variables)?,
+                ])
+            },
+            _ => Err(WrongNumberOfOutputs),
+        }
+    }
+}
\ No newline at end of file
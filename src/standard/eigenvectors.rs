@@ -0,0 +1,108 @@
+//! Computes eigenvectors.
+
+use std::collections::HashMap;
+
+use num_complex::Complex64;
+
+use crate::{
+    Matrix,
+    Expression,
+};
+use crate::error::*;
+
+use super::{
+    StdFunc,
+    Eigenvalues,
+    Lu,
+};
+
+/// Computes the eigenvectors of a real square matrix, as the columns of a matrix,
+/// in the same order as the eigenvalues from `eig`.
+///
+/// Each eigenvector has unit length, and its largest component is real and positive.
+#[derive(Clone)]
+pub struct Eigenvectors;
+
+impl Eigenvectors {
+    /// Evaluates `Eigenvectors` while minimizing heap allocation.
+    ///
+    /// The eigenvectors of a symmetric matrix are orthonormal.  Otherwise, each
+    /// eigenvector is found by inverse iteration from its eigenvalue, so a repeated
+    /// eigenvalue has a single eigenvector.
+    pub fn evalpure(matrix: &Matrix) -> Result<Matrix<Complex64>, Error> {
+        if Eigenvalues::is_symmetric(matrix)? {
+            return Ok(Eigenvalues::symmetric(matrix)?.1.to_complex());
+        }
+
+        let n = matrix.rows();
+        let a = matrix.to_complex();
+        let norm = matrix.vals().iter().map(|x| x.abs()).fold(0.0, f64::max).max(1.0);
+        let mut output = Matrix::new(n, n, vec![Complex64::new(0.0, 0.0); n*n]);
+
+        for (j, value) in Eigenvalues::general(matrix)?.into_iter().enumerate() {
+            // Shift slightly away from the eigenvalue, so that `A - shift*I` can be factorized
+            let shift = value + INVERSE_ITERATION_SHIFT*norm;
+            let mut shifted = a.clone();
+            for i in 0..n {
+                shifted[[i, i]] -= shift;
+            }
+            let lu = Lu::factorize(&shifted)?;
+
+            // Start from a vector that is unlikely to be missing any eigenvector
+            let mut vector = Matrix::new(n, 1, (0..n).map(|i| Complex64::new(1.0 + (i as f64).sqrt()/(n as f64), 0.0)).collect());
+            for _ in 0..INVERSE_ITERATIONS {
+                vector = normalize(lu.solve(&vector)?);
+            }
+
+            for i in 0..n {
+                output[[i, j]] = vector[[i, 0]];
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+/// Size of the shift from each eigenvalue used in inverse iteration, relative
+/// to the largest value of the matrix.
+const INVERSE_ITERATION_SHIFT: f64 = 1e-10;
+
+/// Number of steps of inverse iteration used to find each eigenvector.
+const INVERSE_ITERATIONS: usize = 3;
+
+/// Scales a vector to unit length, with its largest component real and positive.
+fn normalize(vector: Matrix<Complex64>) -> Matrix<Complex64> {
+    let largest = vector.vals().iter().fold(Complex64::new(0.0, 0.0), |m, z| if z.norm() > m.norm() { *z } else { m });
+    let length = vector.vals().iter().map(|z| z.norm_sqr()).sum::<f64>().sqrt();
+
+    if length == 0.0 {
+        return vector;
+    }
+
+    let factor = largest.conj()/(largest.norm()*length);
+    vector.scalar_multiply(factor)
+}
+
+impl StdFunc for Eigenvectors {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        let vectors = Self::evalpure(&args[0])?;
+        if vectors.vals().iter().any(|z| z.im != 0.0) {
+            return Err(ComplexNotSupported);
+        }
+
+        Ok(Matrix::new(vectors.rows(), vectors.cols(), vectors.vals().iter().map(|z| z.re).collect()))
+    }
+
+    /// Real matrices may have complex eigenvectors, like those of `[0 1; -1 0]`.
+    fn call(&self, args: Vec<Expression>, variables: &mut HashMap<String, Expression>) -> Result<Expression, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Expression::from(Self::evalpure(&args[0].to_matrix()?)?).simplify(variables)
+    }
+}
\ No newline at end of file
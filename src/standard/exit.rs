@@ -3,6 +3,7 @@
 use std::process::exit;
 
 use crate::Matrix;
+use crate::error::*;
 
 use super::StdFunc;
 
@@ -10,7 +11,7 @@ use super::StdFunc;
 pub struct Exit;
 
 impl StdFunc for Exit {
-    fn eval(&self, _: Vec<Matrix>) -> Matrix {
+    fn eval(&self, _: Vec<Matrix>) -> Result<Matrix, Error> {
         exit(0);
     }
 }
\ No newline at end of file
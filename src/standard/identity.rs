@@ -9,10 +9,9 @@ use super::StdFunc;
 pub struct Identity;
 
 impl Identity {
-    pub fn evalpure(matrix: &Matrix) -> Matrix {
+    pub fn evalpure(matrix: &Matrix) -> Result<Matrix, Error> {
         if matrix.rows() != 1 || matrix.cols() != 1 {
-            throw(RequiresUnitMatrix);
-            return Matrix::new(0, 0, Vec::new());
+            return Err(RequiresUnitMatrix);
         }
     
         let dim = matrix[[0, 0]] as usize;
@@ -27,15 +26,14 @@ impl Identity {
             }
         }
     
-        output
+        Ok(output)
     }
 }
 
 impl StdFunc for Identity {
-    fn eval(&self, args: Vec<Matrix>) -> Matrix {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
         if args.len() != 1 {
-            throw(WrongNumberOfArgs);
-            return Matrix::new(0, 0, Vec::new());
+            return Err(WrongNumberOfArgs);
         }
 
         Self::evalpure(&args[0])
@@ -0,0 +1,86 @@
+//! Numerically integrates functions of one variable.
+
+use std::collections::HashMap;
+
+use crate::{
+    Matrix,
+    Expression,
+};
+use crate::error::*;
+
+use super::{
+    StdFunc,
+    call_scalar,
+    scalar,
+};
+
+/// Absolute tolerance of the integral.
+const TOLERANCE: f64 = 1e-10;
+
+/// Maximum number of times an interval may be bisected.
+const MAX_DEPTH: usize = 40;
+
+#[derive(Clone)]
+pub struct Integrate;
+
+impl Integrate {
+    /// Integrates `f` from `a` to `b` using adaptive Simpson's rule.
+    pub fn evalpure(f: &dyn Fn(f64) -> Result<f64, Error>, a: f64, b: f64) -> Result<f64, Error> {
+        let fa = f(a)?;
+        let fb = f(b)?;
+        let m = (a + b)/2.0;
+        let fm = f(m)?;
+        let whole = (b - a)/6.0 * (fa + 4.0*fm + fb);
+
+        Self::adapt(f, (a, fa), (m, fm), (b, fb), whole, TOLERANCE, MAX_DEPTH)
+    }
+
+    /// Recursively refines the Simpson's rule estimate `whole` over `[a, b]`.
+    fn adapt(
+        f: &dyn Fn(f64) -> Result<f64, Error>,
+        (a, fa): (f64, f64),
+        (m, fm): (f64, f64),
+        (b, fb): (f64, f64),
+        whole: f64,
+        tolerance: f64,
+        depth: usize,
+    ) -> Result<f64, Error> {
+        let lm = (a + m)/2.0;
+        let rm = (m + b)/2.0;
+        let flm = f(lm)?;
+        let frm = f(rm)?;
+        let left = (m - a)/6.0 * (fa + 4.0*flm + fm);
+        let right = (b - m)/6.0 * (fm + 4.0*frm + fb);
+        let delta = left + right - whole;
+
+        if depth == 0 || delta.abs() <= 15.0*tolerance {
+            return Ok(left + right + delta/15.0);
+        }
+
+        Ok(
+            Self::adapt(f, (a, fa), (lm, flm), (m, fm), left, tolerance/2.0, depth - 1)?
+            + Self::adapt(f, (m, fm), (rm, frm), (b, fb), right, tolerance/2.0, depth - 1)?
+        )
+    }
+}
+
+impl StdFunc for Integrate {
+    fn eval(&self, _args: Vec<Matrix>) -> Result<Matrix, Error> {
+        // `Integrate` requires a function as its first argument
+        Err(ExpectedFunction)
+    }
+
+    fn call(&self, args: Vec<Expression>, variables: &mut HashMap<String, Expression>) -> Result<Expression, Error> {
+        if args.len() != 3 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        let function = &args[0];
+        let a = scalar(&args[1])?;
+        let b = scalar(&args[2])?;
+
+        let integral = Self::evalpure(&|x| call_scalar(function, x), a, b)?;
+
+        Expression::Float (integral).simplify(variables)
+    }
+}
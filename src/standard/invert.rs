@@ -1,66 +1,60 @@
 //! Inverts matrices.
 
-use crate::Matrix;
+use num_complex::Complex64;
+use num_rational::BigRational;
+
+use crate::{
+    Matrix,
+    Uncertain,
+};
 use crate::error::*;
 
 use super::{
     StdFunc,
-    GetMinors,
-    Transpose,
-    Determinant,
+    Lu,
+    Pivot,
 };
 
 #[derive(Clone)]
 pub struct Invert;
 
 impl Invert {
-    /// Evaluates `Invert` while minimizing heap allocation.
-    pub fn evalpure(matrix: &Matrix) -> Matrix {
-        let mut output = matrix.clone();
-        let rows = matrix.rows();
-        let cols = matrix.cols();
-
-        // Compute the determinant of the input matrix
-        let original_det = Determinant::evalpure(matrix);
+    /// Evaluates `Invert` from the LU factorization of the matrix.
+    pub fn evalpure<T: Pivot>(matrix: &Matrix<T>) -> Result<Matrix<T>, Error> {
+        Lu::factorize(matrix)?.inverse()
+    }
+}
 
-        // Step 1: For each cell, compute the determinant of the matrix of minors
-        // determined with respect to that cell
-        for i in 0..rows {
-            for j in 0..cols {
-                let matrix_of_minors = GetMinors::evalpure(matrix, i, j);
-                let det = Determinant::evalpure(&matrix_of_minors);
-                output[[i, j]] = det;
-            }
+impl StdFunc for Invert {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
         }
 
-        // Step 2: For each cell, if `i + j` is odd, multiply the value by -1
-        // Step 4: Divide each value by the determinant of the original matrix
-        for i in 0..rows {
-            for j in 0..cols {
-                if (i + j)%2 != 0 {
-                    output[[i, j]] = output[[i, j]] * -1.0f64;
-                }
-                output[[i, j]] = output[[i, j]] / original_det;
-            }
+        Self::evalpure(&args[0])
+    }
+
+    fn eval_complex(&self, args: Vec<Matrix<Complex64>>) -> Result<Matrix<Complex64>, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
         }
 
-        // Step 3: Transpose the matrix
-        output = Transpose::evalpure(&output);
+        Self::evalpure(&args[0])
+    }
 
-        // Did Step 4 above
+    fn eval_uncertain(&self, args: Vec<Matrix<Uncertain>>) -> Result<Matrix<Uncertain>, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
 
-        // We're done!
-        output
+        Self::evalpure(&args[0])
     }
-}
 
-impl StdFunc for Invert {
-    fn eval(&self, args: Vec<Matrix>) -> Matrix {
+    fn eval_rational(&self, args: Vec<Matrix<BigRational>>) -> Option<Result<Matrix<BigRational>, Error>> {
         if args.len() != 1 {
-            throw(WrongNumberOfArgs);
-            return Matrix::new(0, 0, Vec::new());
+            return Some(Err(WrongNumberOfArgs));
         }
 
-        Self::evalpure(&args[0])
+        Some(Self::evalpure(&args[0]))
     }
 }
\ No newline at end of file
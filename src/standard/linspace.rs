@@ -28,15 +28,18 @@ impl Linspace {
 }
 
 impl StdFunc for Linspace {
-    fn eval(&self, args: Vec<Matrix>) -> Matrix {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.iter().any(|a| a.rows() != 1 || a.cols() != 1) {
+            return Err(RequiresUnitMatrix);
+        }
+
         if args.len() == 2 {
             let count = (args[1][[0, 0]] - args[0][[0, 0]]).round();
-            Self::evalpure(args[0][[0, 0]], args[1][[0, 0]], count)
+            Ok(Self::evalpure(args[0][[0, 0]], args[1][[0, 0]], count))
         } else if args.len() == 3 {
-            Self::evalpure(args[0][[0, 0]], args[1][[0, 0]], args[2][[0, 0]])
+            Ok(Self::evalpure(args[0][[0, 0]], args[1][[0, 0]], args[2][[0, 0]]))
         } else {
-            throw(WrongNumberOfArgs);
-            return Matrix::empty();
+            Err(WrongNumberOfArgs)
         }
     }
 }
\ No newline at end of file
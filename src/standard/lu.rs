@@ -0,0 +1,315 @@
+//! Computes LU factorizations.
+
+use std::collections::HashMap;
+use std::ops::Neg;
+
+use num_complex::Complex64;
+use num_rational::BigRational;
+use num_traits::{
+    Num,
+    ToPrimitive,
+};
+
+use crate::{
+    Matrix,
+    Expression,
+    Uncertain,
+};
+use crate::error::*;
+
+use super::StdFunc;
+
+/// Numbers that can be factorized with partial pivoting.
+pub trait Pivot: Clone + Num + Neg<Output = Self> {
+    /// Whether or not arithmetic on these numbers is exact, in which case only
+    /// zero pivots are singular.
+    const EXACT: bool = false;
+
+    /// Gets the size of this number, used to choose the largest pivot.
+    fn magnitude(&self) -> f64;
+}
+
+impl Pivot for f64 {
+    fn magnitude(&self) -> f64 {
+        self.abs()
+    }
+}
+
+impl Pivot for Complex64 {
+    fn magnitude(&self) -> f64 {
+        self.norm()
+    }
+}
+
+impl Pivot for Uncertain {
+    fn magnitude(&self) -> f64 {
+        self.nominal().abs()
+    }
+}
+
+impl Pivot for BigRational {
+    const EXACT: bool = true;
+
+    fn magnitude(&self) -> f64 {
+        self.to_f64().map_or(f64::INFINITY, f64::abs)
+    }
+}
+
+/// Holds the LU factorization `PA = LU` of a square matrix `A`, where `L` is
+/// unit lower triangular, `U` is upper triangular and `P` is a permutation.
+pub struct LuFactorization<T> {
+    /// `L` and `U`, stored together (the unit diagonal of `L` is not stored).
+    factors: Matrix<T>,
+
+    /// The row of `A` that became each row of `PA`.
+    permutation: Vec<usize>,
+
+    /// Whether `P` is an odd permutation.
+    odd: bool,
+
+    /// Whether `A` is singular, or too close to singular to invert.
+    singular: bool,
+}
+
+impl<T: Pivot> LuFactorization<T> {
+    /// Gets the unit lower triangular factor `L`.
+    pub fn lower(&self) -> Matrix<T> {
+        let n = self.factors.rows();
+        let mut output = Matrix::new(n, n, vec![T::zero(); n*n]);
+
+        for i in 0..n {
+            for j in 0..i {
+                output[[i, j]] = self.factors[[i, j]].clone();
+            }
+            output[[i, i]] = T::one();
+        }
+
+        output
+    }
+
+    /// Gets the upper triangular factor `U`.
+    pub fn upper(&self) -> Matrix<T> {
+        let n = self.factors.rows();
+        let mut output = Matrix::new(n, n, vec![T::zero(); n*n]);
+
+        for i in 0..n {
+            for j in i..n {
+                output[[i, j]] = self.factors[[i, j]].clone();
+            }
+        }
+
+        output
+    }
+
+    /// Gets the permutation matrix `P`.
+    pub fn permutation(&self) -> Matrix<T> {
+        let n = self.factors.rows();
+        let mut output = Matrix::new(n, n, vec![T::zero(); n*n]);
+
+        for (i, p) in self.permutation.iter().enumerate() {
+            output[[i, *p]] = T::one();
+        }
+
+        output
+    }
+
+    /// Computes the determinant of `A`, the product of the pivots.
+    pub fn determinant(&self) -> T {
+        let mut det = if self.odd { -T::one() } else { T::one() };
+
+        for i in 0..self.factors.rows() {
+            det = det*self.factors[[i, i]].clone();
+        }
+
+        det
+    }
+
+    /// Solves `AX = B` for `X` by forward and back substitution.
+    pub fn solve(&self, b: &Matrix<T>) -> Result<Matrix<T>, Error> {
+        let n = self.factors.rows();
+
+        if self.singular {
+            return Err(SingularMatrix);
+        }
+        if b.rows() != n {
+            return Err(ImproperDimensions);
+        }
+
+        let mut output = Matrix::new(n, b.cols(), vec![T::zero(); n*b.cols()]);
+
+        for k in 0..b.cols() {
+            // Solve `Ly = Pb`
+            let mut y: Vec<T> = Vec::with_capacity(n);
+            for i in 0..n {
+                let mut sum = b[[self.permutation[i], k]].clone();
+                for (j, yj) in y.iter().enumerate() {
+                    sum = sum - self.factors[[i, j]].clone()*yj.clone();
+                }
+                y.push(sum);
+            }
+
+            // Solve `Ux = y`
+            for i in (0..n).rev() {
+                let mut sum = y[i].clone();
+                for j in i + 1..n {
+                    sum = sum - self.factors[[i, j]].clone()*output[[j, k]].clone();
+                }
+                output[[i, k]] = sum/self.factors[[i, i]].clone();
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Computes the inverse of `A`.
+    pub fn inverse(&self) -> Result<Matrix<T>, Error> {
+        let n = self.factors.rows();
+        let mut identity = Matrix::new(n, n, vec![T::zero(); n*n]);
+        for i in 0..n {
+            identity[[i, i]] = T::one();
+        }
+
+        self.solve(&identity)
+    }
+}
+
+/// Computes the LU factorization of a matrix.
+///
+/// The output is `L`, `U` and `P` side by side, so that `PA = LU`, or each
+/// factor on its own with `[L, U, P] = lu(A)`.
+#[derive(Clone)]
+pub struct Lu;
+
+impl Lu {
+    /// Factorizes a square matrix by Gaussian elimination with partial pivoting.
+    ///
+    /// A pivot that is zero, or (for floats) smaller than rounding error in the
+    /// largest value of the matrix, marks the matrix as singular.
+    pub fn factorize<T: Pivot>(matrix: &Matrix<T>) -> Result<LuFactorization<T>, Error> {
+        if matrix.rows() != matrix.cols() {
+            return Err(SquareMatrixRequired);
+        }
+
+        let n = matrix.rows();
+        let mut a = matrix.clone();
+        let mut permutation = (0..n).collect::<Vec<usize>>();
+        let mut odd = false;
+        let mut singular = false;
+
+        let largest = matrix.vals().iter().map(|x| x.magnitude()).fold(0.0, f64::max);
+        let tolerance = if T::EXACT { 0.0 } else { (n as f64)*f64::EPSILON*largest };
+
+        for k in 0..n {
+            // Choose the largest nonzero pivot in this column
+            let is_pivot = |x: &T| if T::EXACT { !x.is_zero() } else { x.magnitude() > 0.0 };
+            let pivot = (k..n).filter(|&i| is_pivot(&a[[i, k]])).max_by(|&i, &j| {
+                a[[i, k]].magnitude().total_cmp(&a[[j, k]].magnitude())
+            });
+
+            // A column with no pivot is already eliminated
+            let p = match pivot {
+                Some(p) => p,
+                None => {
+                    singular = true;
+                    continue;
+                },
+            };
+            if a[[p, k]].magnitude() <= tolerance {
+                singular = true;
+            }
+
+            if p != k {
+                for j in 0..n {
+                    let x = a[[p, j]].clone();
+                    a[[p, j]] = a[[k, j]].clone();
+                    a[[k, j]] = x;
+                }
+                permutation.swap(p, k);
+                odd = !odd;
+            }
+
+            for i in k + 1..n {
+                let factor = a[[i, k]].clone()/a[[k, k]].clone();
+                for j in k + 1..n {
+                    a[[i, j]] = a[[i, j]].clone() - factor.clone()*a[[k, j]].clone();
+                }
+                a[[i, k]] = factor;
+            }
+        }
+
+        Ok(LuFactorization {
+            factors: a,
+            permutation,
+            odd,
+            singular,
+        })
+    }
+
+    /// Evaluates `Lu` while minimizing heap allocation.
+    pub fn evalpure<T: Pivot>(matrix: &Matrix<T>) -> Result<Matrix<T>, Error> {
+        let lu = Self::factorize(matrix)?;
+        let n = matrix.rows();
+        let (lower, upper, permutation) = (lu.lower(), lu.upper(), lu.permutation());
+
+        let mut output = Vec::new();
+        for i in 0..n {
+            for factor in [&lower, &upper, &permutation] {
+                for j in 0..n {
+                    output.push(factor[[i, j]].clone());
+                }
+            }
+        }
+
+        Ok(Matrix::new(n, 3*n, output))
+    }
+}
+
+impl StdFunc for Lu {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Self::evalpure(&args[0])
+    }
+
+    fn eval_complex(&self, args: Vec<Matrix<Complex64>>) -> Result<Matrix<Complex64>, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Self::evalpure(&args[0])
+    }
+
+    fn eval_uncertain(&self, args: Vec<Matrix<Uncertain>>) -> Result<Matrix<Uncertain>, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Self::evalpure(&args[0])
+    }
+
+    fn eval_rational(&self, args: Vec<Matrix<BigRational>>) -> Option<Result<Matrix<BigRational>, Error>> {
+        if args.len() != 1 {
+            return Some(Err(WrongNumberOfArgs));
+        }
+
+        Some(Self::evalpure(&args[0]))
+    }
+
+    /// Splits `L`, `U` and `P` into separate values.
+    fn call_multiple(&self, args: Vec<Expression>, count: usize, variables: &mut HashMap<String, Expression>) -> Result<Vec<Expression>, Error> {
+        match count {
+            1 => Ok(vec![self.call(args, variables)?]),
+            3 => match self.call(args, variables)? {
+                Expression::Matrix { rows: n, values, .. } => Ok((0..3).map(|f| Expression::Matrix {
+                    rows: n,
+                    cols: n,
+                    values: (0..n*n).map(|k| values[(k/n)*3*n + f*n + k%n].clone()).collect(),
+                }).collect()),
+                _ => Err(ImproperDimensions),
+            },
+            _ => Err(WrongNumberOfOutputs),
+        }
+    }
+}
\ No newline at end of file
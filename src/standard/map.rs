@@ -0,0 +1,50 @@
+//! Applies a function to each value of a matrix.
+
+use std::collections::HashMap;
+
+use crate::{
+    Matrix,
+    Expression,
+};
+use crate::error::*;
+
+use super::StdFunc;
+
+#[derive(Clone)]
+pub struct Map;
+
+impl StdFunc for Map {
+    fn eval(&self, _args: Vec<Matrix>) -> Result<Matrix, Error> {
+        // `Map` requires a function as its first argument
+        Err(ExpectedFunction)
+    }
+
+    fn call(&self, args: Vec<Expression>, variables: &mut HashMap<String, Expression>) -> Result<Expression, Error> {
+        if args.len() != 2 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        let function = &args[0];
+
+        // Apply the function to each value of the matrix (or to the number)
+        let (rows, cols, values) = match &args[1] {
+            Expression::Matrix {
+                rows: r,
+                cols: c,
+                values: v,
+            } => (*r, *c, v.to_owned()),
+            other => (1, 1, vec![other.to_owned()]),
+        };
+
+        let mut output = Vec::new();
+        for value in values {
+            output.push(function.apply(vec![value])?);
+        }
+
+        Expression::Matrix {
+            rows,
+            cols,
+            values: output,
+        }.simplify(variables)
+    }
+}
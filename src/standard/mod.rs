@@ -8,7 +8,6 @@ mod determinant;
 mod transpose;
 mod identity;
 mod invert;
-mod get_minors;
 mod sqrt;
 mod sin;
 mod cos;
@@ -19,15 +18,49 @@ mod linspace;
 mod plot;
 mod rotation;
 mod routh;
+mod map;
+mod integrate;
+mod root;
+mod any;
+mod all;
+mod where_;
+mod re;
+mod im;
+mod abs;
+mod arg;
+mod conj;
+mod polar;
+mod to;
+mod lu;
+mod cholesky;
+mod qr;
+mod solve;
+mod eigenvalues;
+mod eigenvectors;
+mod singular_values;
+mod rank;
+mod pseudo_inverse;
+mod null_space;
+mod column_space;
+mod condition_number;
 
 use std::{
     collections::HashMap,
+    f64::consts::{
+        E,
+        PI,
+        TAU,
+    },
     rc::Rc,
 };
 
+use num_complex::Complex64;
+use num_rational::BigRational;
+
 use crate::{
     Matrix,
     Expression,
+    Uncertain,
 };
 use crate::error::*;
 
@@ -35,7 +68,6 @@ pub use determinant::Determinant;
 pub use transpose::Transpose;
 pub use identity::Identity;
 pub use invert::Invert;
-pub use get_minors::GetMinors;
 pub use sqrt::Sqrt;
 pub use sin::Sin;
 pub use cos::Cos;
@@ -45,28 +77,158 @@ pub use dot::Dot;
 pub use linspace::Linspace;
 pub use plot::Plt;
 pub use rotation::Rotation2d;
-pub use routh::Routh;
+pub use map::Map;
+pub use integrate::Integrate;
+pub use root::Root;
+pub use any::Any;
+pub use all::All;
+pub use where_::Where;
+pub use re::Re;
+pub use im::Im;
+pub use abs::Abs;
+pub use arg::Arg;
+pub use conj::Conj;
+pub use polar::Polar;
+pub use to::To;
+pub use lu::{
+    Lu,
+    Pivot,
+};
+pub use cholesky::Cholesky;
+pub use qr::Qr;
+pub use solve::Solve;
+pub use eigenvalues::Eigenvalues;
+pub use eigenvectors::Eigenvectors;
+pub use singular_values::SingularValues;
+pub use rank::Rank;
+pub use pseudo_inverse::PseudoInverse;
+pub use null_space::NullSpace;
+pub use column_space::ColumnSpace;
+pub use condition_number::ConditionNumber;
 
 /// Any function available in the standard library satisfies this trait.
 pub trait StdFunc {
-    fn eval(&self, args: Vec<Matrix>) -> Matrix;
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error>;
+
+    /// Evaluates this function on complex matrices.
+    /// 
+    /// By default, functions do not accept complex arguments.
+    fn eval_complex(&self, _args: Vec<Matrix<Complex64>>) -> Result<Matrix<Complex64>, Error> {
+        Err(ComplexNotSupported)
+    }
+
+    /// Evaluates this function on matrices of uncertain values.
+    /// 
+    /// By default, functions do not accept uncertain arguments.
+    fn eval_uncertain(&self, _args: Vec<Matrix<Uncertain>>) -> Result<Matrix<Uncertain>, Error> {
+        Err(UncertaintyNotSupported)
+    }
+
+    /// Evaluates this function exactly on matrices of rational numbers.
+    /// 
+    /// By default, functions have no exact form and return `None`, so that
+    /// they are evaluated with floats instead.
+    fn eval_rational(&self, _args: Vec<Matrix<BigRational>>) -> Option<Result<Matrix<BigRational>, Error>> {
+        None
+    }
+
+    /// Calls this function on a list of simplified arguments.
+    /// 
+    /// By default, each argument is converted into a matrix and passed to
+    /// `StdFunc::eval`, or to `StdFunc::eval_complex` if any argument is complex,
+    /// or to `StdFunc::eval_uncertain` if any argument is uncertain.  If every
+    /// argument is exact, `StdFunc::eval_rational` is tried first.
+    /// Functions that accept other kinds of arguments (such as closures) may
+    /// override this to call back into the evaluator.
+    fn call(&self, args: Vec<Expression>, variables: &mut HashMap<String, Expression>) -> Result<Expression, Error> {
+        if args.iter().any(|x| x.is_uncertain()) {
+            return self.call_uncertain(args, variables);
+        }
+        if args.iter().any(|x| x.is_complex()) {
+            return self.call_complex(args, variables);
+        }
+        if !args.is_empty() && args.iter().all(|x| x.is_exact()) {
+            let mut matrices = Vec::new();
+            for arg in &args {
+                matrices.push(arg.to_rational_matrix()?);
+            }
+
+            if let Some(result) = self.eval_rational(matrices) {
+                return Expression::from(result?).simplify(variables);
+            }
+        }
+
+        let mut matrices = Vec::new();
+        for arg in args {
+            matrices.push(arg.to_matrix()?);
+        }
+
+        Expression::from(self.eval(matrices)?).simplify(variables)
+    }
+
+    /// Gets the positions of the arguments that name units, like the target of
+    /// `to(x, ft)`.  These arguments are read as units, unless they are variables.
+    fn unit_args(&self) -> Vec<usize> {
+        Vec::new()
+    }
+
+    /// Calls this function on a list of simplified arguments, returning `count`
+    /// values, as in `[Q, R] = qr(A)`.
+    /// 
+    /// By default, functions return a single value.
+    fn call_multiple(&self, args: Vec<Expression>, count: usize, variables: &mut HashMap<String, Expression>) -> Result<Vec<Expression>, Error> {
+        match count {
+            1 => Ok(vec![self.call(args, variables)?]),
+            _ => Err(WrongNumberOfOutputs),
+        }
+    }
+
+    /// Calls this function on a list of simplified arguments, converting each
+    /// argument into a complex matrix and passing it to `StdFunc::eval_complex`.
+    fn call_complex(&self, args: Vec<Expression>, variables: &mut HashMap<String, Expression>) -> Result<Expression, Error> {
+        let mut matrices = Vec::new();
+        for arg in args {
+            matrices.push(arg.to_complex_matrix()?);
+        }
+
+        Expression::from(self.eval_complex(matrices)?).simplify(variables)
+    }
+
+    /// Calls this function on a list of simplified arguments, converting each
+    /// argument into a matrix of uncertain values and passing it to `StdFunc::eval_uncertain`.
+    fn call_uncertain(&self, args: Vec<Expression>, variables: &mut HashMap<String, Expression>) -> Result<Expression, Error> {
+        let mut matrices = Vec::new();
+        for arg in args {
+            matrices.push(arg.to_uncertain_matrix()?);
+        }
+
+        Expression::from(self.eval_uncertain(matrices)?).simplify(variables)
+    }
 }
 
 
-/// A unit struct passed by `get_std_function` when a function is not found.
-/// 
-/// This allows the interpreter to continue working without panicking.
-pub struct Error;
+/// Converts a number (or unit matrix) into an `f64`.
+pub fn scalar(expression: &Expression) -> Result<f64, Error> {
+    let matrix = expression.to_matrix()?;
 
-impl StdFunc for Error {
-    fn eval(&self, _args: Vec<Matrix>) -> Matrix {
-        Matrix::new(0, 0, Vec::new())
+    if matrix.rows() != 1 || matrix.cols() != 1 {
+        return Err(RequiresUnitMatrix);
     }
+
+    Ok(matrix[[0, 0]])
+}
+
+
+/// Calls a function or closure on a single number, expecting a number in return.
+/// 
+/// This allows higher-order functions to treat Elemental functions as `f64 -> f64`.
+pub fn call_scalar(function: &Expression, x: f64) -> Result<f64, Error> {
+    scalar(&function.apply(vec![Expression::Float (x)])?)
 }
 
 
 /// Get a function pointer based on that function's name.
-pub fn get_std_function(name: String) -> Rc<dyn StdFunc> {
+pub fn get_std_function(name: String) -> Result<Rc<dyn StdFunc>, Error> {
     let mut hashmap: HashMap<String, Rc<dyn StdFunc>> = HashMap::new();
 
     // Declarative standard library begins here
@@ -83,14 +245,36 @@ pub fn get_std_function(name: String) -> Rc<dyn StdFunc> {
     hashmap.insert("linspace".to_string(), Rc::new(Linspace {}));
     hashmap.insert("plot".to_string(), Rc::new(Plt {}));
     hashmap.insert("rot2".to_string(), Rc::new(Rotation2d {}));
+    hashmap.insert("map".to_string(), Rc::new(Map {}));
+    hashmap.insert("integrate".to_string(), Rc::new(Integrate {}));
+    hashmap.insert("root".to_string(), Rc::new(Root {}));
+    hashmap.insert("any".to_string(), Rc::new(Any {}));
+    hashmap.insert("all".to_string(), Rc::new(All {}));
+    hashmap.insert("where".to_string(), Rc::new(Where {}));
+    hashmap.insert("re".to_string(), Rc::new(Re {}));
+    hashmap.insert("im".to_string(), Rc::new(Im {}));
+    hashmap.insert("abs".to_string(), Rc::new(Abs {}));
+    hashmap.insert("arg".to_string(), Rc::new(Arg {}));
+    hashmap.insert("conj".to_string(), Rc::new(Conj {}));
+    hashmap.insert("polar".to_string(), Rc::new(Polar {}));
+    hashmap.insert("to".to_string(), Rc::new(To {}));
+    hashmap.insert("lu".to_string(), Rc::new(Lu {}));
+    hashmap.insert("qr".to_string(), Rc::new(Qr {}));
+    hashmap.insert("chol".to_string(), Rc::new(Cholesky {}));
+    hashmap.insert("solve".to_string(), Rc::new(Solve {}));
+    hashmap.insert("eig".to_string(), Rc::new(Eigenvalues {}));
+    hashmap.insert("eigvec".to_string(), Rc::new(Eigenvectors {}));
+    hashmap.insert("svd".to_string(), Rc::new(SingularValues {}));
+    hashmap.insert("rank".to_string(), Rc::new(Rank {}));
+    hashmap.insert("pinv".to_string(), Rc::new(PseudoInverse {}));
+    hashmap.insert("null".to_string(), Rc::new(NullSpace {}));
+    hashmap.insert("orth".to_string(), Rc::new(ColumnSpace {}));
+    hashmap.insert("cond".to_string(), Rc::new(ConditionNumber {}));
     // hashmap.insert("routh".to_string(), Rc::new(Routh {}));
 
     match hashmap.get(&name) {
-        Some(f) => f.clone(),
-        None => {
-            throw(CouldNotFindFunction);
-            Rc::new(Error {})
-        },
+        Some(f) => Ok(f.clone()),
+        None => Err(CouldNotFindFunction),
     }
 }
 
@@ -98,7 +282,13 @@ pub fn get_std_function(name: String) -> Rc<dyn StdFunc> {
 /// Gets the standard variables stored in the program.
 pub fn get_std_variables() -> HashMap<String, Expression> {
     HashMap::from([
-        ("pi".to_string(), Expression::Float (3.141592653)),
-        ("deg".to_string(), Expression::Float (3.141592653/180.0)),
+        ("pi".to_string(), Expression::Float (PI)),
+        ("π".to_string(), Expression::Float (PI)),
+        ("tau".to_string(), Expression::Float (TAU)),
+        ("τ".to_string(), Expression::Float (TAU)),
+        ("e".to_string(), Expression::Float (E)),
+        ("deg".to_string(), Expression::Float (PI/180.0)),
+        ("true".to_string(), Expression::Bool (true)),
+        ("false".to_string(), Expression::Bool (false)),
     ])
 }
\ No newline at end of file
@@ -0,0 +1,45 @@
+//! Computes null spaces.
+
+use crate::Matrix;
+use crate::error::*;
+
+use super::{
+    StdFunc,
+    SingularValues,
+};
+
+/// Computes an orthonormal basis for the null space of a matrix, as the
+/// columns of a matrix, so that `A*null(A)` is zero.
+#[derive(Clone)]
+pub struct NullSpace;
+
+impl NullSpace {
+    /// Evaluates `NullSpace` while minimizing heap allocation.
+    pub fn evalpure(matrix: &Matrix) -> Result<Matrix, Error> {
+        let n = matrix.cols();
+        let (_, s, v) = SingularValues::decompose(matrix)?;
+        let tolerance = SingularValues::tolerance(matrix, &s);
+
+        // The right singular vectors of zero singular values span the null space
+        let columns = (0..n).filter(|k| s[*k] <= tolerance).collect::<Vec<usize>>();
+
+        let mut output = Vec::new();
+        for i in 0..n {
+            for k in &columns {
+                output.push(v[[i, *k]]);
+            }
+        }
+
+        Ok(Matrix::new(n, columns.len(), output))
+    }
+}
+
+impl StdFunc for NullSpace {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Self::evalpure(&args[0])
+    }
+}
\ No newline at end of file
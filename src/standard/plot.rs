@@ -20,15 +20,14 @@ pub struct Plt;
 
 impl Plt {
     /// Evaluates `Plt` while minimizing heap allocation.
-    pub fn evalpure(vec1: &Matrix, vec2: &Matrix) -> Matrix {
+    pub fn evalpure(vec1: &Matrix, vec2: &Matrix) -> Result<Matrix, Error> {
         let a = vec1.vals();
         let b = vec2.vals();
 
         if vec1.rows() != vec2.rows()
             || vec1.cols() != vec2.cols()
         {
-            throw(ImproperDimensions);
-            return Matrix::empty();
+            return Err(ImproperDimensions);
         }
 
         // Zip the two vector values into one vector of tuples
@@ -50,24 +49,23 @@ impl Plt {
         // Render the plot in the terminal
         match Page::single(&v).dimensions(150, 40).to_text() {
             Ok(p) => println!("{}", p),
-            Err(_) => throw(CouldNotDisplayPlot),
+            Err(_) => return Err(CouldNotDisplayPlot),
         };
         
         // Save the plot to a file
         match Page::single(&v).save("plot.svg") {
             Ok(_) => (),
-            Err(_) => throw(CouldNotWriteToFile),
+            Err(_) => return Err(CouldNotWriteToFile),
         };
 
-        Matrix::empty()
+        Ok(Matrix::empty())
     }
 }
 
 impl StdFunc for Plt {
-    fn eval(&self, args: Vec<Matrix>) -> Matrix {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
         if args.len() != 2 {
-            throw(WrongNumberOfArgs);
-            return Matrix::new(0, 0, Vec::new());
+            return Err(WrongNumberOfArgs);
         }
 
         Self::evalpure(&args[0], &args[1])
@@ -0,0 +1,54 @@
+//! Converts numbers into polar form.
+
+use num_complex::{
+    Complex64,
+    ComplexFloat,
+};
+
+use crate::Matrix;
+use crate::error::*;
+
+use super::StdFunc;
+
+/// Converts each value of a matrix into polar form.
+/// 
+/// Each value becomes a row of the output, holding its magnitude
+/// and its argument (in radians).
+#[derive(Clone)]
+pub struct Polar;
+
+impl Polar {
+    /// Evaluates `Polar` while minimizing heap allocation.
+    pub fn evalpure<T: ComplexFloat<Real = f64>>(matrix: &Matrix<T>) -> Matrix {
+        let mut output = Vec::new();
+
+        for v in matrix.vals() {
+            output.push(v.abs());
+            output.push(v.arg());
+        }
+
+        Matrix::new(
+            matrix.vals().len(),
+            2,
+            output,
+        )
+    }
+}
+
+impl StdFunc for Polar {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Self::evalpure(&args[0]))
+    }
+
+    fn eval_complex(&self, args: Vec<Matrix<Complex64>>) -> Result<Matrix<Complex64>, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Self::evalpure(&args[0]).to_complex())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,47 @@
+//! Computes pseudoinverses.
+
+use crate::Matrix;
+use crate::error::*;
+
+use super::{
+    StdFunc,
+    SingularValues,
+};
+
+/// Computes the Moore-Penrose pseudoinverse of a matrix of any shape.
+/// 
+/// Singular values that are zero (to working precision) are left out, so
+/// `pinv(A)*b` is the least-squares solution of `Ax = b` with the smallest length.
+#[derive(Clone)]
+pub struct PseudoInverse;
+
+impl PseudoInverse {
+    /// Evaluates `PseudoInverse` while minimizing heap allocation.
+    pub fn evalpure(matrix: &Matrix) -> Result<Matrix, Error> {
+        let (m, n) = (matrix.rows(), matrix.cols());
+        let (u, s, v) = SingularValues::decompose(matrix)?;
+        let tolerance = SingularValues::tolerance(matrix, &s);
+
+        // Compute `V inv(S) U'`, keeping only the first `m` rows of `U`
+        let mut output = Matrix::new(n, m, vec![0.0; n*m]);
+        for (k, sigma) in s.iter().enumerate().filter(|(_, x)| **x > tolerance) {
+            for i in 0..n {
+                for j in 0..m {
+                    output[[i, j]] += v[[i, k]]*u[[j, k]]/sigma;
+                }
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+impl StdFunc for PseudoInverse {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Self::evalpure(&args[0])
+    }
+}
\ No newline at end of file
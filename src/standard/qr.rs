@@ -0,0 +1,165 @@
+//! Computes QR factorizations.
+
+use std::collections::HashMap;
+
+use crate::{
+    Matrix,
+    Expression,
+};
+use crate::error::*;
+
+use super::StdFunc;
+
+/// Computes the QR factorization `A = QR` of a matrix, where `Q` is orthogonal
+/// and `R` is upper triangular.
+///
+/// `qr(A)` is `R`, and `[Q, R] = qr(A)` assigns both factors.  `qr(A, 0)` is
+/// the economy factorization, which keeps only the first `n` columns of `Q` and
+/// rows of `R` for an `m`x`n` matrix with `m > n`.
+#[derive(Clone)]
+pub struct Qr;
+
+impl Qr {
+    /// Factorizes a matrix by Householder reflections, returning `Q` and `R`.
+    /// 
+    /// For an `m`x`n` matrix, `Q` is `m`x`m` and `R` is `m`x`n`.
+    pub fn factorize(matrix: &Matrix) -> (Matrix, Matrix) {
+        let (m, n) = (matrix.rows(), matrix.cols());
+        let mut q = Matrix::new(m, m, vec![0.0; m*m]);
+        let mut r = matrix.clone();
+
+        for i in 0..m {
+            q[[i, i]] = 1.0;
+        }
+
+        for k in 0..n.min(m.saturating_sub(1)) {
+            // Reflect the column below the diagonal onto the diagonal, choosing
+            // the direction that avoids cancellation
+            let norm = (k..m).map(|i| r[[i, k]]*r[[i, k]]).sum::<f64>().sqrt();
+            if norm == 0.0 {
+                continue;
+            }
+            let alpha = if r[[k, k]] > 0.0 { -norm } else { norm };
+
+            let mut v = (k..m).map(|i| r[[i, k]]).collect::<Vec<f64>>();
+            v[0] -= alpha;
+            let length = v.iter().map(|x| x*x).sum::<f64>();
+            if length == 0.0 {
+                continue;
+            }
+
+            // Apply the reflection `I - 2vv'/v'v` to `R` from the left
+            for j in 0..n {
+                let f = 2.0*v.iter().enumerate().map(|(l, x)| x*r[[k + l, j]]).sum::<f64>()/length;
+                for (l, x) in v.iter().enumerate() {
+                    r[[k + l, j]] -= f*x;
+                }
+            }
+
+            // ...and to `Q` from the right
+            for i in 0..m {
+                let f = 2.0*v.iter().enumerate().map(|(l, x)| q[[i, k + l]]*x).sum::<f64>()/length;
+                for (l, x) in v.iter().enumerate() {
+                    q[[i, k + l]] -= f*x;
+                }
+            }
+
+            // The reflection leaves exact zeros below the diagonal
+            r[[k, k]] = alpha;
+            for i in k + 1..m {
+                r[[i, k]] = 0.0;
+            }
+        }
+
+        (q, r)
+    }
+
+    /// Evaluates `Qr` while minimizing heap allocation, returning `Q` and `R`.
+    pub fn evalpure(matrix: &Matrix, economy: bool) -> (Matrix, Matrix) {
+        let (m, n) = (matrix.rows(), matrix.cols());
+        let (q, r) = Self::factorize(matrix);
+
+        if !economy || m <= n {
+            return (q, r);
+        }
+
+        let q = Matrix::new(m, n, (0..m*n).map(|k| q[[k/n, k%n]]).collect());
+        let r = Matrix::new(n, n, r.vals()[..n*n].to_vec());
+
+        (q, r)
+    }
+
+    /// Checks the arguments of `qr`, returning whether the economy factorization
+    /// was requested.
+    fn economy(args: &[Matrix]) -> Result<bool, Error> {
+        match args.len() {
+            1 => Ok(false),
+            2 if args[1].rows() != 1 || args[1].cols() != 1 => Err(RequiresUnitMatrix),
+            2 => Ok(args[1][[0, 0]] == 0.0),
+            _ => Err(WrongNumberOfArgs),
+        }
+    }
+
+    /// Finds the least-squares solution `X` of `AX = B`, for a matrix `A` with
+    /// at least as many rows as columns.
+    /// 
+    /// Returns an error if the columns of `A` are (nearly) linearly dependent.
+    pub fn least_squares(matrix: &Matrix, b: &Matrix) -> Result<Matrix, Error> {
+        let (m, n) = (matrix.rows(), matrix.cols());
+
+        if m < n || b.rows() != m {
+            return Err(ImproperDimensions);
+        }
+
+        let (q, r) = Self::factorize(matrix);
+
+        let largest = (0..n).map(|i| r[[i, i]].abs()).fold(0.0, f64::max);
+        if (0..n).any(|i| r[[i, i]].abs() <= (m as f64)*f64::EPSILON*largest) {
+            return Err(SingularMatrix);
+        }
+
+        let mut output = Matrix::new(n, b.cols(), vec![0.0; n*b.cols()]);
+
+        for k in 0..b.cols() {
+            // Only the first `n` values of `Q'b` can be fit
+            let y = (0..n).map(|i| (0..m).map(|l| q[[l, i]]*b[[l, k]]).sum::<f64>()).collect::<Vec<f64>>();
+
+            // Solve `Rx = y`
+            for i in (0..n).rev() {
+                let mut sum = y[i];
+                for j in i + 1..n {
+                    sum -= r[[i, j]]*output[[j, k]];
+                }
+                output[[i, k]] = sum/r[[i, i]];
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+impl StdFunc for Qr {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        let economy = Self::economy(&args)?;
+
+        Ok(Self::evalpure(&args[0], economy).1)
+    }
+
+    fn call_multiple(&self, args: Vec<Expression>, count: usize, variables: &mut HashMap<String, Expression>) -> Result<Vec<Expression>, Error> {
+        match count {
+            1 => Ok(vec![self.call(args, variables)?]),
+            2 => {
+                if args.is_empty() || args.len() > 2 {
+                    return Err(WrongNumberOfArgs);
+                }
+
+                let args = args.iter().map(|a| a.to_matrix()).collect::<Result<Vec<Matrix>, Error>>()?;
+                let economy = Self::economy(&args)?;
+                let (q, r) = Self::evalpure(&args[0], economy);
+
+                Ok(vec![Expression::from(q).simplify(variables)?, Expression::from(r).simplify(variables)?])
+            },
+            _ => Err(WrongNumberOfOutputs),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,34 @@
+//! Computes the rank of matrices.
+
+use crate::Matrix;
+use crate::error::*;
+
+use super::{
+    StdFunc,
+    SingularValues,
+};
+
+/// Computes the rank of a matrix, the number of its singular values
+/// that are not zero (to working precision).
+#[derive(Clone)]
+pub struct Rank;
+
+impl Rank {
+    /// Evaluates `Rank` while minimizing heap allocation.
+    pub fn evalpure(matrix: &Matrix) -> Result<usize, Error> {
+        let (_, s, _) = SingularValues::decompose(matrix)?;
+        let tolerance = SingularValues::tolerance(matrix, &s);
+
+        Ok(s.iter().filter(|x| **x > tolerance).count())
+    }
+}
+
+impl StdFunc for Rank {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Matrix::new(1, 1, vec![Self::evalpure(&args[0])? as f64]))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,51 @@
+//! Computes real parts.
+
+use num_complex::{
+    Complex64,
+    ComplexFloat,
+};
+
+use crate::Matrix;
+use crate::error::*;
+
+use super::StdFunc;
+
+#[derive(Clone)]
+pub struct Re;
+
+impl Re {
+    /// Evaluates `Re` while minimizing heap allocation.
+    pub fn evalpure<T: ComplexFloat<Real = f64>>(matrix: &Matrix<T>) -> Matrix<f64> {
+        let mut output = Vec::new();
+        let rows = matrix.rows();
+        let cols = matrix.cols();
+
+        for v in matrix.vals() {
+            output.push(v.re());
+        }
+
+        Matrix::new(
+            rows,
+            cols,
+            output,
+        )
+    }
+}
+
+impl StdFunc for Re {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Self::evalpure(&args[0]))
+    }
+
+    fn eval_complex(&self, args: Vec<Matrix<Complex64>>) -> Result<Matrix<Complex64>, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Self::evalpure(&args[0]).to_complex())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,118 @@
+//! Finds roots of functions of one variable.
+
+use std::collections::HashMap;
+
+use crate::{
+    Matrix,
+    Expression,
+};
+use crate::error::*;
+
+use super::{
+    StdFunc,
+    call_scalar,
+    scalar,
+};
+
+/// Absolute tolerance of the root.
+const TOLERANCE: f64 = 1e-12;
+
+/// Maximum number of iterations before giving up.
+const MAX_ITERATIONS: usize = 200;
+
+#[derive(Clone)]
+pub struct Root;
+
+impl Root {
+    /// Finds a root of `f` near `x0` using the secant method.
+    pub fn secant(f: &dyn Fn(f64) -> Result<f64, Error>, x0: f64) -> Result<f64, Error> {
+        let mut x_prev = x0;
+        let mut x = if x0 == 0.0 { 1e-4 } else { x0 * (1.0 + 1e-4) };
+        let mut f_prev = f(x_prev)?;
+
+        for _ in 0..MAX_ITERATIONS {
+            let fx = f(x)?;
+            if fx == 0.0 {
+                return Ok(x);
+            }
+
+            let slope = (fx - f_prev)/(x - x_prev);
+            if slope == 0.0 || !slope.is_finite() {
+                return Err(DidNotConverge);
+            }
+
+            let next = x - fx/slope;
+            if (next - x).abs() <= TOLERANCE * (1.0 + next.abs()) {
+                return Ok(next);
+            }
+
+            x_prev = x;
+            f_prev = fx;
+            x = next;
+        }
+
+        Err(DidNotConverge)
+    }
+
+    /// Finds a root of `f` between `a` and `b` using bisection.
+    /// 
+    /// `f(a)` and `f(b)` must have opposite signs.
+    pub fn bisect(f: &dyn Fn(f64) -> Result<f64, Error>, a: f64, b: f64) -> Result<f64, Error> {
+        let (mut a, mut b) = (a, b);
+        let mut fa = f(a)?;
+        let fb = f(b)?;
+
+        if fa == 0.0 {
+            return Ok(a);
+        } else if fb == 0.0 {
+            return Ok(b);
+        } else if fa.signum() == fb.signum() {
+            return Err(RootNotBracketed);
+        }
+
+        for _ in 0..MAX_ITERATIONS {
+            let m = (a + b)/2.0;
+            let fm = f(m)?;
+
+            if fm == 0.0 || (b - a).abs()/2.0 <= TOLERANCE * (1.0 + m.abs()) {
+                return Ok(m);
+            }
+
+            if fm.signum() == fa.signum() {
+                a = m;
+                fa = fm;
+            } else {
+                b = m;
+            }
+        }
+
+        Ok((a + b)/2.0)
+    }
+}
+
+impl StdFunc for Root {
+    fn eval(&self, _args: Vec<Matrix>) -> Result<Matrix, Error> {
+        // `Root` requires a function as its first argument
+        Err(ExpectedFunction)
+    }
+
+    fn call(&self, args: Vec<Expression>, variables: &mut HashMap<String, Expression>) -> Result<Expression, Error> {
+        let function = match args.first() {
+            Some(f) => f,
+            None => return Err(WrongNumberOfArgs),
+        };
+        let f = |x| call_scalar(function, x);
+
+        let root = if args.len() == 2 {
+            // Search near an initial guess
+            Self::secant(&f, scalar(&args[1])?)?
+        } else if args.len() == 3 {
+            // Search within an interval
+            Self::bisect(&f, scalar(&args[1])?, scalar(&args[2])?)?
+        } else {
+            return Err(WrongNumberOfArgs);
+        };
+
+        Expression::Float (root).simplify(variables)
+    }
+}
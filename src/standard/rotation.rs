@@ -9,15 +9,14 @@ use super::StdFunc;
 pub struct Rotation2d;
 
 impl Rotation2d {
-    pub fn evalpure(matrix: &Matrix) -> Matrix {
+    pub fn evalpure(matrix: &Matrix) -> Result<Matrix, Error> {
         if matrix.rows() != 1 || matrix.cols() != 1 {
-            throw(RequiresUnitMatrix);
-            return Matrix::new(0, 0, Vec::new());
+            return Err(RequiresUnitMatrix);
         }
     
         let angle = matrix[[0, 0]];
     
-        Matrix::new(
+        Ok(Matrix::new(
             2,
             2,
             vec![
@@ -26,15 +25,14 @@ impl Rotation2d {
                 angle.sin(),
                 angle.cos(),
             ]
-        )
+        ))
     }
 }
 
 impl StdFunc for Rotation2d {
-    fn eval(&self, args: Vec<Matrix>) -> Matrix {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
         if args.len() != 1 {
-            throw(WrongNumberOfArgs);
-            return Matrix::new(0, 0, Vec::new());
+            return Err(WrongNumberOfArgs);
         }
 
         Self::evalpure(&args[0])
@@ -10,10 +10,9 @@ pub struct Routh;
 
 impl Routh {
     #[allow(dead_code)]
-    pub fn evalpure(matrix: &Matrix) -> Matrix {
+    pub fn evalpure(matrix: &Matrix) -> Result<Matrix, Error> {
         if matrix.rows() != 1 {
-            throw(RequiresUnitMatrix);
-            return Matrix::new(0, 0, Vec::new());
+            return Err(RequiresUnitMatrix);
         }
 
         todo!()
@@ -22,7 +21,7 @@ impl Routh {
 
 impl StdFunc for Routh {
     #[allow(unused_variables)]
-    fn eval(&self, args: Vec<Matrix>) -> Matrix {
-        Matrix::new(0, 0, Vec::new())
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        Ok(Matrix::new(0, 0, Vec::new()))
     }
 }
\ No newline at end of file
@@ -0,0 +1,187 @@
+//! Computes singular value decompositions.
+
+use std::collections::HashMap;
+
+use crate::{
+    Matrix,
+    Expression,
+};
+use crate::error::*;
+
+use super::{
+    StdFunc,
+    Qr,
+};
+
+/// Largest number of sweeps of the Jacobi method before giving up.
+const MAX_SWEEPS: usize = 60;
+
+/// Computes the singular values of a matrix of any shape, as a column vector
+/// in descending order.
+///
+/// `[U, S, V] = svd(A)` assigns the full decomposition `A = USV'`.
+#[derive(Clone)]
+pub struct SingularValues;
+
+impl SingularValues {
+    /// Evaluates `SingularValues` while minimizing heap allocation.
+    pub fn evalpure(matrix: &Matrix) -> Result<Matrix, Error> {
+        let (_, s, _) = Self::decompose(matrix)?;
+        let k = matrix.rows().min(matrix.cols());
+
+        Ok(Matrix::new(k, 1, s[..k].to_vec()))
+    }
+
+    /// Decomposes an `m`x`n` matrix `A` into `USV'` by the one-sided Jacobi method,
+    /// which rotates pairs of columns of `A` until they are orthogonal.
+    ///
+    /// Returns the columns of `U` (`max(m, n)`x`n`, with only the first `m` rows
+    /// belonging to `A`), the `n` singular values in descending order and the
+    /// square, orthogonal matrix `V`.  If `A` has fewer rows than columns, it is
+    /// padded with rows of zeros, so that `V` is always complete; the extra
+    /// singular values are zero.  Columns of `U` for zero singular values are zero.
+    pub fn decompose(matrix: &Matrix) -> Result<(Matrix, Vec<f64>, Matrix), Error> {
+        let (m, n) = (matrix.rows(), matrix.cols());
+        let rows = m.max(n);
+
+        let mut u = Matrix::new(rows, n, vec![0.0; rows*n]);
+        for i in 0..m {
+            for j in 0..n {
+                u[[i, j]] = matrix[[i, j]];
+            }
+        }
+        let mut v = Matrix::new(n, n, vec![0.0; n*n]);
+        for i in 0..n {
+            v[[i, i]] = 1.0;
+        }
+
+        let mut converged = false;
+        for _ in 0..MAX_SWEEPS {
+            let mut rotated = false;
+
+            for p in 0..n {
+                for q in p + 1..n {
+                    let alpha = (0..rows).map(|i| u[[i, p]]*u[[i, p]]).sum::<f64>();
+                    let beta = (0..rows).map(|i| u[[i, q]]*u[[i, q]]).sum::<f64>();
+                    let gamma = (0..rows).map(|i| u[[i, p]]*u[[i, q]]).sum::<f64>();
+
+                    if gamma == 0.0 || gamma.abs() <= f64::EPSILON*(alpha*beta).sqrt() {
+                        continue;
+                    }
+                    rotated = true;
+
+                    // Choose the smaller rotation that makes the columns orthogonal
+                    let zeta = (beta - alpha)/(2.0*gamma);
+                    let t = if zeta == 0.0 {
+                        1.0
+                    } else {
+                        zeta.signum()/(zeta.abs() + (zeta*zeta + 1.0).sqrt())
+                    };
+                    let c = 1.0/(t*t + 1.0).sqrt();
+                    let s = t*c;
+
+                    for i in 0..rows {
+                        let (x, y) = (u[[i, p]], u[[i, q]]);
+                        u[[i, p]] = c*x - s*y;
+                        u[[i, q]] = s*x + c*y;
+                    }
+                    for i in 0..n {
+                        let (x, y) = (v[[i, p]], v[[i, q]]);
+                        v[[i, p]] = c*x - s*y;
+                        v[[i, q]] = s*x + c*y;
+                    }
+                }
+            }
+
+            if !rotated {
+                converged = true;
+                break;
+            }
+        }
+
+        if !converged {
+            return Err(DidNotConverge);
+        }
+
+        // The singular values are the lengths of the columns
+        let lengths = (0..n).map(|j| (0..rows).map(|i| u[[i, j]]*u[[i, j]]).sum::<f64>().sqrt()).collect::<Vec<f64>>();
+        let mut order = (0..n).collect::<Vec<usize>>();
+        order.sort_by(|&i, &j| lengths[j].total_cmp(&lengths[i]));
+
+        let mut left = Matrix::new(rows, n, vec![0.0; rows*n]);
+        let mut right = Matrix::new(n, n, vec![0.0; n*n]);
+        for (j, &k) in order.iter().enumerate() {
+            for i in 0..rows {
+                if lengths[k] > 0.0 {
+                    left[[i, j]] = u[[i, k]]/lengths[k];
+                }
+            }
+            for i in 0..n {
+                right[[i, j]] = v[[i, k]];
+            }
+        }
+
+        Ok((left, order.iter().map(|&k| lengths[k]).collect(), right))
+    }
+
+    /// Decomposes an `m`x`n` matrix `A` into `USV'`, where `U` (`m`x`m`) and
+    /// `V` (`n`x`n`) are orthogonal and `S` (`m`x`n`) is diagonal.
+    pub fn full(matrix: &Matrix) -> Result<(Matrix, Matrix, Matrix), Error> {
+        let (m, n) = (matrix.rows(), matrix.cols());
+        let (left, s, v) = Self::decompose(matrix)?;
+
+        // Only the columns of `U` for nonzero singular values are known, and
+        // the rest of `Q` from their QR factorization completes them
+        let r = s.iter().filter(|x| **x > Self::tolerance(matrix, &s)).count();
+        let (mut u, _) = Qr::factorize(&Matrix::new(m, r, (0..m*r).map(|k| left[[k/r, k%r]]).collect()));
+        for i in 0..m {
+            for j in 0..r {
+                u[[i, j]] = left[[i, j]];
+            }
+        }
+
+        let mut diagonal = Matrix::new(m, n, vec![0.0; m*n]);
+        for k in 0..m.min(n) {
+            diagonal[[k, k]] = s[k];
+        }
+
+        Ok((u, diagonal, v))
+    }
+
+    /// Gets the tolerance below which singular values of a matrix are treated as zero.
+    pub fn tolerance(matrix: &Matrix, values: &[f64]) -> f64 {
+        let largest = values.iter().cloned().fold(0.0, f64::max);
+
+        (matrix.rows().max(matrix.cols()) as f64)*f64::EPSILON*largest
+    }
+}
+
+impl StdFunc for SingularValues {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Self::evalpure(&args[0])
+    }
+
+    fn call_multiple(&self, args: Vec<Expression>, count: usize, variables: &mut HashMap<String, Expression>) -> Result<Vec<Expression>, Error> {
+        match count {
+            1 => Ok(vec![self.call(args, variables)?]),
+            3 => {
+                if args.len() != 1 {
+                    return Err(WrongNumberOfArgs);
+                }
+
+                let (u, s, v) = Self::full(&args[0].to_matrix()?)?;
+
+                Ok(vec![
+                    Expression::from(u).simplify(variables)?,
+                    Expression::from(s).simplify(variables)?,
+                    Expression::from(v).simplify(variables)?,
+                ])
+            },
+            _ => Err(WrongNumberOfOutputs),
+        }
+    }
+}
\ No newline at end of file
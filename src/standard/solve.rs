@@ -0,0 +1,96 @@
+//! Solves systems of linear equations.
+
+use num_complex::Complex64;
+use num_rational::BigRational;
+
+use crate::{
+    Matrix,
+    Uncertain,
+};
+use crate::error::*;
+
+use super::{
+    StdFunc,
+    Cholesky,
+    Lu,
+    Pivot,
+    Qr,
+};
+
+/// Solves `AX = B` for `X`, as in `solve(A, b)` or `A \ b`.
+/// 
+/// Each column of `B` is a separate right-hand side.  Square systems are
+/// solved by Cholesky factorization if `A` is symmetric positive definite,
+/// or by LU factorization otherwise.  Over-determined systems are solved
+/// in the least-squares sense by QR factorization, which is only supported
+/// for real matrices, so complex and uncertain systems must be square.
+#[derive(Clone)]
+pub struct Solve;
+
+impl Solve {
+    /// Evaluates `Solve` while minimizing heap allocation.
+    pub fn evalpure(matrix: &Matrix, b: &Matrix) -> Result<Matrix, Error> {
+        if matrix.rows() != b.rows() {
+            return Err(ImproperDimensions);
+        }
+
+        if matrix.rows() == matrix.cols() {
+            match Cholesky::factorize(matrix) {
+                Ok(lower) => Cholesky::solve(&lower, b),
+                Err(_) => Self::square(matrix, b),
+            }
+        } else if matrix.rows() > matrix.cols() {
+            Qr::least_squares(matrix, b)
+        } else {
+            Err(ImproperDimensions)
+        }
+    }
+
+    /// Solves a square system by LU factorization.
+    pub fn square<T: Pivot>(matrix: &Matrix<T>, b: &Matrix<T>) -> Result<Matrix<T>, Error> {
+        if matrix.rows() != matrix.cols() {
+            return Err(SquareMatrixRequired);
+        }
+
+        Lu::factorize(matrix)?.solve(b)
+    }
+}
+
+impl StdFunc for Solve {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 2 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Self::evalpure(&args[0], &args[1])
+    }
+
+    fn eval_complex(&self, args: Vec<Matrix<Complex64>>) -> Result<Matrix<Complex64>, Error> {
+        if args.len() != 2 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Self::square(&args[0], &args[1])
+    }
+
+    fn eval_uncertain(&self, args: Vec<Matrix<Uncertain>>) -> Result<Matrix<Uncertain>, Error> {
+        if args.len() != 2 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Self::square(&args[0], &args[1])
+    }
+
+    /// Square systems of exact values have exact solutions, while least-squares
+    /// solutions are found with floats.
+    fn eval_rational(&self, args: Vec<Matrix<BigRational>>) -> Option<Result<Matrix<BigRational>, Error>> {
+        if args.len() != 2 {
+            return Some(Err(WrongNumberOfArgs));
+        }
+        if args[0].rows() != args[0].cols() {
+            return None;
+        }
+
+        Some(Self::square(&args[0], &args[1]))
+    }
+}
\ No newline at end of file
@@ -1,6 +1,17 @@
 //! Computes square roots.
 
-use crate::Matrix;
+use std::collections::HashMap;
+
+use num_complex::{
+    Complex64,
+    ComplexFloat,
+};
+
+use crate::{
+    Matrix,
+    Expression,
+    Uncertain,
+};
 use crate::error::*;
 
 use super::StdFunc;
@@ -10,7 +21,7 @@ pub struct Sqrt;
 
 impl Sqrt {
     /// Evaluates `Sqrt` while minimizing heap allocation.
-    pub fn evalpure(matrix: &Matrix) -> Matrix {
+    pub fn evalpure<T: ComplexFloat>(matrix: &Matrix<T>) -> Matrix<T> {
         let mut output = Vec::new();
         let rows = matrix.rows();
         let cols = matrix.cols();
@@ -28,12 +39,57 @@ impl Sqrt {
 }
 
 impl StdFunc for Sqrt {
-    fn eval(&self, args: Vec<Matrix>) -> Matrix {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Self::evalpure(&args[0]))
+    }
+
+    fn eval_uncertain(&self, args: Vec<Matrix<Uncertain>>) -> Result<Matrix<Uncertain>, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        let vals = args[0].vals().iter().map(|v| v.sqrt()).collect::<Vec<Uncertain>>();
+        Ok(Matrix::new(args[0].rows(), args[0].cols(), vals))
+    }
+
+    fn eval_complex(&self, args: Vec<Matrix<Complex64>>) -> Result<Matrix<Complex64>, Error> {
         if args.len() != 1 {
-            throw(WrongNumberOfArgs);
-            return Matrix::new(0, 0, Vec::new());
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Self::evalpure(&args[0]))
+    }
+
+    /// Negative numbers have imaginary square roots, so `sqrt(-4)` is `2i`.
+    /// 
+    /// The square root of a quantity has the square root of its units, so
+    /// `sqrt(4 [m^2])` is `2 m`.
+    fn call(&self, args: Vec<Expression>, variables: &mut HashMap<String, Expression>) -> Result<Expression, Error> {
+        if let [Expression::Quantity { value: v, unit: u }] = args.as_slice() {
+            return Ok(Expression::Quantity {
+                value: Box::new(self.call(vec![*v.to_owned()], variables)?),
+                unit: u.root(2).ok_or(IncompatibleUnits)?,
+            });
+        }
+
+        if args.iter().any(|x| x.is_uncertain()) {
+            return self.call_uncertain(args, variables);
+        }
+
+        let negative = |x: &Expression| x.to_matrix().is_ok_and(|m| m.vals().iter().any(|v| *v < 0.0));
+        if args.iter().any(|x| x.is_complex() || negative(x)) {
+            return self.call_complex(args, variables);
+        }
+
+        let mut matrices = Vec::new();
+        for arg in args {
+            matrices.push(arg.to_matrix()?);
         }
 
-        Self::evalpure(&args[0])
+        Expression::from(self.eval(matrices)?).simplify(variables)
     }
 }
\ No newline at end of file
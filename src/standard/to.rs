@@ -0,0 +1,51 @@
+//! Converts quantities between units.
+
+use std::collections::HashMap;
+
+use crate::{
+    expression::scale,
+    Matrix,
+    Expression,
+};
+use crate::error::*;
+
+use super::StdFunc;
+
+/// Converts a quantity into the units of another quantity, as in `to(x, ft)`.
+#[derive(Clone)]
+pub struct To;
+
+impl StdFunc for To {
+    /// The target of a conversion is a unit, as in `to(x, ft)` or `to(v, km/h)`.
+    fn unit_args(&self) -> Vec<usize> {
+        vec![1]
+    }
+
+    /// Numbers without units are unchanged by conversion.
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 2 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(args[0].to_owned())
+    }
+
+    fn call(&self, args: Vec<Expression>, variables: &mut HashMap<String, Expression>) -> Result<Expression, Error> {
+        if args.len() != 2 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        let mut args = args.into_iter();
+        let (value, from) = args.next().unwrap().to_quantity();
+        let (_, to) = args.next().unwrap().to_quantity();
+
+        if from.dimension() != to.dimension() {
+            return Err(IncompatibleUnits);
+        }
+
+        Expression::Quantity {
+            value: Box::new(scale(value, from.scale()/to.scale())?),
+            unit: to,
+        }.simplify(variables)
+    }
+}
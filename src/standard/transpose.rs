@@ -1,6 +1,13 @@
 //! Transposes matrices.
 
-use crate::Matrix;
+use num_complex::Complex64;
+use num_rational::BigRational;
+use num_traits::Num;
+
+use crate::{
+    Matrix,
+    Uncertain,
+};
 use crate::error::*;
 
 use super::StdFunc;
@@ -11,14 +18,14 @@ pub struct Transpose;
 
 impl Transpose {
     /// Evaluates `Transpose` while minimizing heap allocation.
-    pub fn evalpure(matrix: &Matrix) -> Matrix {
+    pub fn evalpure<T: Clone + Num>(matrix: &Matrix<T>) -> Matrix<T> {
         let mut result = matrix.copy_vals();
         let cols = matrix.rows();
         let rows = matrix.cols();
 
         for i in 0..matrix.rows() {
             for j in 0..matrix.cols() {
-                result[j*cols + i] = matrix[[i, j]];
+                result[j*cols + i] = matrix[[i, j]].clone();
             }
         }
 
@@ -27,12 +34,35 @@ impl Transpose {
 }
 
 impl StdFunc for Transpose {
-    fn eval(&self, args: Vec<Matrix>) -> Matrix {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Self::evalpure(&args[0]))
+    }
+
+    fn eval_complex(&self, args: Vec<Matrix<Complex64>>) -> Result<Matrix<Complex64>, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Self::evalpure(&args[0]))
+    }
+
+    fn eval_uncertain(&self, args: Vec<Matrix<Uncertain>>) -> Result<Matrix<Uncertain>, Error> {
+        if args.len() != 1 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Ok(Self::evalpure(&args[0]))
+    }
+
+    fn eval_rational(&self, args: Vec<Matrix<BigRational>>) -> Option<Result<Matrix<BigRational>, Error>> {
         if args.len() != 1 {
-            throw(WrongNumberOfArgs);
-            return Matrix::new(0, 0, Vec::new());
+            return Some(Err(WrongNumberOfArgs));
         }
 
-        Self::evalpure(&args[0])
+        Some(Ok(Self::evalpure(&args[0])))
     }
 }
\ No newline at end of file
@@ -0,0 +1,57 @@
+//! Chooses values element-wise from two matrices, according to a mask.
+
+use crate::Matrix;
+use crate::error::*;
+
+use super::StdFunc;
+
+#[derive(Clone)]
+pub struct Where;
+
+impl Where {
+    /// Evaluates `Where` while minimizing heap allocation.
+    /// 
+    /// Each value is taken from `a` where the mask is true (nonzero) and from `b`
+    /// where it is not.  `a` and `b` may be numbers (unit matrices), or matrices of
+    /// the same dimensions as the mask.
+    pub fn evalpure(mask: &Matrix, a: &Matrix, b: &Matrix) -> Result<Matrix, Error> {
+        let rows = mask.rows();
+        let cols = mask.cols();
+
+        // Gets the `k`th value of a matrix, broadcasting unit matrices
+        let value = |matrix: &Matrix, k: usize| -> Result<f64, Error> {
+            if matrix.rows() == 1 && matrix.cols() == 1 {
+                Ok(matrix[[0, 0]])
+            } else if matrix.rows() == rows && matrix.cols() == cols {
+                Ok(matrix.vals()[k])
+            } else {
+                Err(ImproperDimensions)
+            }
+        };
+
+        let mut output = Vec::new();
+        for (k, m) in mask.vals().iter().enumerate() {
+            if *m != 0.0 {
+                output.push(value(a, k)?);
+            } else {
+                output.push(value(b, k)?);
+            }
+        }
+
+        Ok(Matrix::new(
+            rows,
+            cols,
+            output,
+        ))
+    }
+}
+
+impl StdFunc for Where {
+    fn eval(&self, args: Vec<Matrix>) -> Result<Matrix, Error> {
+        if args.len() != 3 {
+            return Err(WrongNumberOfArgs);
+        }
+
+        Self::evalpure(&args[0], &args[1], &args[2])
+    }
+}
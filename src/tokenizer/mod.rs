@@ -1,5 +1,8 @@
 //! Provides a tokenizer for the Elemental interpreter.
 
+use num_bigint::BigInt;
+use unicode_xid::UnicodeXID;
+
 use crate::error::*;
 
 /// Outlines the types of tokens that Elemental can process.
@@ -8,27 +11,127 @@ pub enum TokenClass {
     Identifier,
     Int,
     Float,
+    Imaginary,
     Assignment,
     Plus,
     Minus,
+    PlusMinus,
     Multiply,
     Divide,
+    Backslash,
+    Caret,
+    DotMultiply,
+    DotDivide,
+    DotCaret,
     Eq,
+    NotEq,
+    Less,
+    Greater,
+    LessEq,
+    GreaterEq,
+    And,
+    Or,
+    Not,
     Semicolon,
     Comma,
+    Colon,
     Newline,
     Prime,
     OpenParen,
     CloseParen,
     OpenBracket,
     CloseBracket,
+    OpenBrace,
+    CloseBrace,
+    Arrow,
+    If,
+    Else,
+    For,
+    In,
+    While,
+}
+
+/// Holds a region of source code, from a starting line and column up to
+/// (but not including) an ending line and column.
+/// 
+/// Lines and columns are counted from 1.  The default span is empty and
+/// refers to no particular location.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Span {
+    line: usize,
+    col: usize,
+    end_line: usize,
+    end_col: usize,
 }
 
-/// Holds a token's class and its value.
+impl Span {
+    /// Constructs a new `Span` from a starting and ending `(line, column)` position.
+    pub fn new(start: (usize, usize), end: (usize, usize)) -> Self {
+        Self {
+            line: start.0,
+            col: start.1,
+            end_line: end.0,
+            end_col: end.1,
+        }
+    }
+
+    /// Constructs a new `Span` covering this span, the given span, and everything in between.
+    pub fn to(&self, other: Span) -> Self {
+        Self {
+            end_line: other.end_line,
+            end_col: other.end_col,
+            ..*self
+        }
+    }
+
+    /// Gets the line on which the span starts.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Gets the column at which the span starts.
+    pub fn col(&self) -> usize {
+        self.col
+    }
+
+    /// Gets the line on which the span ends.
+    pub fn end_line(&self) -> usize {
+        self.end_line
+    }
+
+    /// Gets the column at which the span ends.
+    pub fn end_col(&self) -> usize {
+        self.end_col
+    }
+
+    /// Checks whether or not this span refers to a location in the source.
+    pub fn is_known(&self) -> bool {
+        self.line != 0
+    }
+
+    /// Gets the source code covered by this span.
+    pub fn excerpt(&self, source: &str) -> String {
+        let mut excerpt = String::new();
+        for (i, line) in source.lines().enumerate().take(self.end_line).skip(self.line.saturating_sub(1)) {
+            let start = if i + 1 == self.line { self.col - 1 } else { 0 };
+            let end = if i + 1 == self.end_line { self.end_col - 1 } else { line.chars().count() };
+
+            if !excerpt.is_empty() {
+                excerpt.push('\n');
+            }
+            excerpt.extend(line.chars().skip(start).take(end.saturating_sub(start)));
+        }
+        excerpt
+    }
+}
+
+
+/// Holds a token's class, its value, and its location in the source.
 #[derive(Clone, Debug)]
 pub struct Token {
     class: TokenClass,
     value: String,
+    span: Span,
 }
 
 impl Token {
@@ -37,6 +140,7 @@ impl Token {
         Self {
             class,
             value,
+            span: Span::default(),
         }
     }
 
@@ -50,6 +154,11 @@ impl Token {
         self.value.to_owned()
     }
 
+    /// Gets the location of the token in the source.
+    pub fn get_span(&self) -> Span {
+        self.span
+    }
+
     /// Checks if the token is in the given class.
     pub fn check(&self, class: TokenClass) -> bool {
         self.class == class
@@ -61,6 +170,8 @@ impl Token {
 pub struct CharStream {
     characters: Vec<char>,
     index: usize,
+    line: usize,
+    col: usize,
 }
 
 impl CharStream {
@@ -72,6 +183,8 @@ impl CharStream {
         Self {
             characters,
             index,
+            line: 1,
+            col: 1,
         }
     }
 
@@ -82,10 +195,24 @@ impl CharStream {
             None
         } else {
             self.index += 1;
+
+            // Keep track of our position in the source
+            if character == Some('\n') {
+                self.line += 1;
+                self.col = 1;
+            } else {
+                self.col += 1;
+            }
+
             character
         }
     }
 
+    /// Gets the `(line, column)` position of the next character in the stream.
+    pub fn position(&self) -> (usize, usize) {
+        (self.line, self.col)
+    }
+
     /// Peeks at the next character in the stream.
     pub fn peek(&self) -> Option<char> {
         if self.index >= self.characters.len() {
@@ -99,7 +226,7 @@ impl CharStream {
     /// 
     /// `Self::lookahead(0)` is equivalent to `Self::peek()`.
     pub fn lookahead(&self, n: usize) -> Option<char> {
-        if self.index >= self.characters.len() {
+        if self.index + n >= self.characters.len() {
             None
         } else {
             Some (self.characters[self.index + n])
@@ -122,148 +249,466 @@ impl CharStream {
         current
     }
 
-    /// Skips comments.
+    /// Gets a numeric literal, starting with the given character.
+    /// 
+    /// Literals may be hexadecimal (`0x1F`) or binary (`0b101`) integers, or decimal
+    /// numbers with an optional exponent (`6.022e23`) or SI suffix (`4.7k`).  Digits
+    /// may be separated by underscores (`1_000_000`).
+    /// 
+    /// A `'.'` that begins an element-wise operator (as in `2.*x`) is not
+    /// included in the literal.
+    pub fn get_numeric(&mut self, first: char) -> String {
+        let mut current = first.to_string();
+
+        // Hexadecimal and binary integers
+        if first == '0' {
+            let digits = match self.peek() {
+                Some('x' | 'X') => HEXADECIMAL,
+                Some('b' | 'B') => BINARY,
+                _ => "",
+            };
+
+            if self.lookahead(1).is_some_and(|c| digits.contains(c)) {
+                current.push(self.next().unwrap());
+                current.push_str(&self.get(digits));
+                return current;
+            }
+        }
+
+        while let Some(c) = self.peek() {
+            if !NUMERIC.contains(c) || (c == '.' && self.lookahead(1).is_some_and(|n| ELEMENTWISE.contains(n))) {
+                break;
+            }
+            self.next();
+            current.push(c);
+        }
+
+        // An exponent must have at least one digit, so `2e` is `2` followed by `e`
+        let is_digit = |c: Option<char>| c.is_some_and(|c| c.is_ascii_digit());
+        if matches!(self.peek(), Some('e' | 'E')) && (
+            is_digit(self.lookahead(1))
+            || (matches!(self.lookahead(1), Some('+' | '-')) && is_digit(self.lookahead(2)))
+        ) {
+            current.push(self.next().unwrap());
+            current.push(self.next().unwrap());
+            current.push_str(&self.get(DIGITS));
+        }
+
+        // An SI suffix cannot be the start of an identifier, so `2kg` is `2` followed by `kg`
+        if self.peek().is_some_and(|c| SI_PREFIXES.contains(&c))
+            && !self.lookahead(1).is_some_and(is_identifier) {
+            current.push(self.next().unwrap());
+        }
+
+        current
+    }
+
+    /// Gets the remainder of an identifier.
+    pub fn get_identifier(&mut self) -> String {
+        let mut current = String::new();
+        while let Some(c) = self.peek() {
+            if is_identifier(c) {
+                self.next();
+                current.push(c);
+            } else {
+                break;
+            }
+        }
+        current
+    }
+
+    /// Skips a comment, up to (but not including) the end of the line.
     pub fn skip_comments(&mut self) {
-        while self.peek() == Some('/') && self.lookahead(1) == Some('/') {
-            while self.peek() != Some('\n') {
+        if self.peek() == Some('/') && self.lookahead(1) == Some('/') {
+            while self.peek().is_some() && self.peek() != Some('\n') {
                 self.next();
             }
-            // Consume the newline
-            self.next();
         }
     }
 }
 
 
-/// Characters that can compose an identifier.
+/// Checks whether or not a character can start an identifier.
+/// 
+/// Identifiers follow the Unicode standard (UAX #31), so `θ` and `ω_n` are
+/// identifiers, as are names starting with an underscore.
+fn is_identifier_start(c: char) -> bool {
+    c == '_' || c.is_xid_start()
+}
+
+
+/// Checks whether or not a character can continue an identifier.
 /// 
 /// Please note that, though numbers are included here, identifiers cannot start
-/// with a numeric digit (`'0'..='9'`).
-const IDENTIFIER: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_";
+/// with a numeric digit (`'0'..='9'`).  The middle dot (`·`) is excluded, as it
+/// is used for multiplication.
+fn is_identifier(c: char) -> bool {
+    c != '·' && c.is_xid_continue()
+}
 
 
 /// Numeric values.  These can compose a numeric literal.
 /// 
 /// Please note that numeric literals cannot start with `'.'`.
-const NUMERIC: &str = "01235456789.";
+const NUMERIC: &str = "0123456789._";
+
+
+/// Decimal digits, which can compose an exponent.
+const DIGITS: &str = "0123456789_";
+
+
+/// Hexadecimal digits, which can compose a literal starting with `0x`.
+const HEXADECIMAL: &str = "0123456789abcdefABCDEF_";
+
+
+/// Binary digits, which can compose a literal starting with `0b`.
+const BINARY: &str = "01_";
+
+
+/// SI prefixes, which can follow a numeric literal.
+/// 
+/// Milli (`m`) and tera (`T`) are left out, as they are also units, so `5m` is
+/// five metres (like `5 m`) and `1T` is one tesla.
+const SI_PREFIXES: [char; 9] = ['f', 'p', 'n', 'u', 'µ', 'k', 'M', 'G', 'P'];
+
+
+/// Gets the power of ten represented by an SI prefix.
+pub fn si_prefix(prefix: char) -> Option<i32> {
+    match prefix {
+        'f' => Some (-15),
+        'p' => Some (-12),
+        'n' => Some (-9),
+        'u' | 'µ' => Some (-6),
+        'm' => Some (-3),
+        'k' => Some (3),
+        'M' => Some (6),
+        'G' => Some (9),
+        'T' => Some (12),
+        'P' => Some (15),
+        _ => None,
+    }
+}
+
+
+/// Imaginary units, which can follow a numeric literal to make it imaginary (`4i` or `4j`).
+const IMAGINARY: &str = "ij";
+
+
+/// Operators that can follow `'.'` to form an element-wise operator.
+const ELEMENTWISE: &str = "*/^";
 
 
 /// Separators & whitespace.  To be ignored.
-const SEPARATORS: &str = " \t\n\r";
+/// 
+/// Please note that newlines are not included here, as they separate statements.
+const SEPARATORS: &str = " \t\r";
+
+
+/// Converts a raw numeric string into an `Int` or `Float` token.
+/// 
+/// Integers of any size are `Int` tokens, holding their decimal digits.
+/// 
+/// The caller is responsible for attaching a span to the error, if any.
+fn numeric(raw: String) -> Result<Token, Error> {
+    let raw = raw.replace('_', "");
+
+    // Hexadecimal and binary literals are always integers
+    let radix = match raw.get(..2) {
+        Some("0x" | "0X") => Some (16),
+        Some("0b" | "0B") => Some (2),
+        _ => None,
+    };
+    if let Some(r) = radix {
+        return match BigInt::parse_bytes(&raw.as_bytes()[2..], r) {
+            Some(i) => Ok(Token::new(TokenClass::Int, i.to_string())),
+            None => Err(CouldNotParseNumeric),
+        };
+    }
+
+    // Literals with SI suffixes are scaled accordingly, keeping any exponent
+    if let Some(power) = raw.chars().last().and_then(si_prefix) {
+        let mantissa = raw.trim_end_matches(|c: char| !c.is_ascii_digit() && c != '.');
+        let (mantissa, exponent) = match mantissa.split_once(['e', 'E']) {
+            Some((m, e)) => (m, str::parse::<i32>(e).map_err(|_| CouldNotParseNumeric)?),
+            None => (mantissa, 0),
+        };
+
+        // Scale in decimal, so that `10u` is exactly `1e-5`
+        return match str::parse::<f64>(&format!("{}e{}", mantissa, exponent + power)) {
+            Ok(f) => Ok(Token::new(TokenClass::Float, f.to_string())),
+            Err(_) => Err(CouldNotParseNumeric),
+        };
+    }
+
+    if str::parse::<BigInt>(&raw).is_ok() {
+        Ok(Token::new(TokenClass::Int, raw))
+    } else if str::parse::<f64>(&raw).is_ok() {
+        Ok(Token::new(TokenClass::Float, raw))
+    } else {
+        Err(CouldNotParseNumeric)
+    }
+}
+
+
+/// Gets the class of a keyword, or `None` if the given name is not a keyword.
+/// 
+/// Keywords cannot be used as identifiers.
+fn keyword(name: &str) -> Option<TokenClass> {
+    match name {
+        "if" => Some (TokenClass::If),
+        "else" => Some (TokenClass::Else),
+        "for" => Some (TokenClass::For),
+        "in" => Some (TokenClass::In),
+        "while" => Some (TokenClass::While),
+        _ => None,
+    }
+}
 
 
 /// Holds a stream of tokens.
 pub struct Tokenizer {
     tokens: Vec<Token>,
     index: usize,
+    delimiters: Vec<TokenClass>,
+    unclosed: usize,
 }
 
 impl Tokenizer {
     /// Constructs a new token stream from a `String`.
-    pub fn from(input: String) -> Self {
+    pub fn from(input: String) -> Result<Self, Error> {
         let index = 0;
         let mut charstream = CharStream::from(input);
         let mut tokens = Vec::new();
 
-        // Skip any comments
-        charstream.skip_comments();
+        // Keep track of the parentheses, brackets and braces that are open,
+        // as newlines mean different things inside each of them
+        let mut open: Vec<TokenClass> = Vec::new();
+
+        loop {
+            // Skip any comments
+            charstream.skip_comments();
+
+            let c = match charstream.peek() {
+                Some(c) => c,
+                None => break,
+            };
+
+            // Remember where this token starts
+            let start = charstream.position();
+            charstream.next();
 
-        while let Some(c) = charstream.next() {
             if SEPARATORS.contains(c) {
                 continue;
             }
 
             let token = match c {
-                'a'..='z' | 'A'..='Z' | '_' => {
+                c if is_identifier_start(c) => {
                     let name = format!(
                         "{}{}",
                         c,
-                        charstream.get(IDENTIFIER),
+                        charstream.get_identifier(),
                     );
-                    Token::new(TokenClass::Identifier, name)
+                    match keyword(&name) {
+                        Some(class) => Token::new(class, name),
+                        None => Token::new(TokenClass::Identifier, name),
+                    }
                 },
                 '0'..='9' => {
-                    let raw = format!(
-                        "{}{}",
-                        c,
-                        charstream.get(NUMERIC),
-                    );
-                    
-                    let token = match str::parse::<i64>(&raw) {
-                        Ok(_) => Token::new(TokenClass::Int, raw),
-                        Err(_) => match str::parse::<f64>(&raw) {
-                            Ok(_) => Token::new(TokenClass::Float, raw),
-                            Err(_) => {
-                                throw(CouldNotParseNumeric);
-                                Token::new(TokenClass::Float, "0.0".to_string())
-                            },
-                        },
-                    };
-                    token
+                    let raw = charstream.get_numeric(c);
+                    let token = numeric(raw).map_err(|e| e.with_span(Span::new(start, charstream.position())))?;
+
+                    // An imaginary suffix cannot be the start of an identifier, so `2in` is `2` followed by `in`
+                    if charstream.peek().is_some_and(|c| IMAGINARY.contains(c))
+                        && !charstream.lookahead(1).is_some_and(is_identifier) {
+                        charstream.next();
+                        Token::new(TokenClass::Imaginary, token.get_value())
+                    } else {
+                        token
+                    }
                 },
                 '=' => if charstream.peek() == Some('=') {
+                    charstream.next();
                     Token::new(TokenClass::Eq, "==".to_string())
-                } else if let Some(_) = charstream.peek() {
+                } else if charstream.peek().is_some() {
                     Token::new(TokenClass::Assignment, "=".to_string())
                 } else {
-                    throw(UnexpectedEof);
+                    return Err(UnexpectedEof.with_span(Span::new(start, charstream.position())));
+                },
+                '!' => if charstream.peek() == Some('=') {
+                    charstream.next();
+                    Token::new(TokenClass::NotEq, "!=".to_string())
+                } else {
+                    Token::new(TokenClass::Not, '!'.to_string())
+                },
+                '<' => if charstream.peek() == Some('=') {
+                    charstream.next();
+                    Token::new(TokenClass::LessEq, "<=".to_string())
+                } else {
+                    Token::new(TokenClass::Less, '<'.to_string())
+                },
+                '>' => if charstream.peek() == Some('=') {
+                    charstream.next();
+                    Token::new(TokenClass::GreaterEq, ">=".to_string())
+                } else {
+                    Token::new(TokenClass::Greater, '>'.to_string())
+                },
+                '&' if charstream.peek() == Some('&') => {
+                    charstream.next();
+                    Token::new(TokenClass::And, "&&".to_string())
+                },
+                '|' if charstream.peek() == Some('|') => {
+                    charstream.next();
+                    Token::new(TokenClass::Or, "||".to_string())
+                },
+                // Inside parentheses, statements continue onto the next line
+                '\n' => if open.last() == Some(&TokenClass::OpenParen) {
+                    continue;
+                } else {
                     Token::new(TokenClass::Newline, '\n'.to_string())
                 },
-                '\n' => Token::new(TokenClass::Newline, '\n'.to_string()),
-                '+' => Token::new(TokenClass::Plus, '+'.to_string()),
-                '-' => {
-                    let chr = match charstream.peek() {
-                        Some(p) => p,
-                        None => {
-                            throw(UnexpectedEof);
-                            '\n'
-                        },
-                    };
-                    if NUMERIC.contains(chr) {
-                        let raw = format!(
-                            "{}{}",
-                            c,
-                            charstream.get(NUMERIC),
-                        );
-                        
-                        let token = match str::parse::<i64>(&raw) {
-                            Ok(_) => Token::new(TokenClass::Int, raw),
-                            Err(_) => match str::parse::<f64>(&raw) {
-                                Ok(_) => Token::new(TokenClass::Float, raw),
-                                Err(_) => {
-                                    throw(CouldNotParseNumeric);
-                                    Token::new(TokenClass::Float, "0.0".to_string())
-                                },
-                            },
-                        };
-                        token
-                    } else {
-                        Token::new(TokenClass::Minus, '-'.to_string())
-                    }
-                }
+                // `+-` is only an alias for `±` with whitespace on both sides, as in
+                // `2 +- 0.1`, so `a+-b` and `a + -b` are still `a + (-b)`
+                '+' => if charstream.peek() == Some('-')
+                    && tokens.last().is_none_or(|t: &Token| (t.get_span().end_line(), t.get_span().end_col()) != start)
+                    && charstream.lookahead(1).is_none_or(|c| SEPARATORS.contains(c) || c == '\n') {
+                    charstream.next();
+                    Token::new(TokenClass::PlusMinus, '±'.to_string())
+                } else {
+                    Token::new(TokenClass::Plus, '+'.to_string())
+                },
+                '±' => Token::new(TokenClass::PlusMinus, '±'.to_string()),
+                '-' => if charstream.peek() == Some('>') {
+                    charstream.next();
+                    Token::new(TokenClass::Arrow, "->".to_string())
+                } else {
+                    Token::new(TokenClass::Minus, '-'.to_string())
+                },
                 '*' => Token::new(TokenClass::Multiply, '*'.to_string()),
                 '/' => Token::new(TokenClass::Divide, '/'.to_string()),
+                '\\' => Token::new(TokenClass::Backslash, '\\'.to_string()),
+
+                // Mathematical symbols are aliases for their ASCII counterparts
+                '×' | '·' => Token::new(TokenClass::Multiply, '*'.to_string()),
+                '÷' => Token::new(TokenClass::Divide, '/'.to_string()),
+                '−' => Token::new(TokenClass::Minus, '-'.to_string()),
+                '≤' => Token::new(TokenClass::LessEq, "<=".to_string()),
+                '≥' => Token::new(TokenClass::GreaterEq, ">=".to_string()),
+                '≠' => Token::new(TokenClass::NotEq, "!=".to_string()),
+                '^' => Token::new(TokenClass::Caret, '^'.to_string()),
+                '.' => match charstream.peek() {
+                    Some('*') => {
+                        charstream.next();
+                        Token::new(TokenClass::DotMultiply, ".*".to_string())
+                    },
+                    Some('/') => {
+                        charstream.next();
+                        Token::new(TokenClass::DotDivide, "./".to_string())
+                    },
+                    Some('^') => {
+                        charstream.next();
+                        Token::new(TokenClass::DotCaret, ".^".to_string())
+                    },
+                    _ => return Err(UnexpectedEof.with_span(Span::new(start, charstream.position()))),
+                },
                 ';' => Token::new(TokenClass::Semicolon, ';'.to_string()),
                 '(' => Token::new(TokenClass::OpenParen, '('.to_string()),
                 ')' => Token::new(TokenClass::CloseParen, ')'.to_string()),
                 '[' => Token::new(TokenClass::OpenBracket, '['.to_string()),
                 ']' => Token::new(TokenClass::CloseBracket, ']'.to_string()),
+                '{' => Token::new(TokenClass::OpenBrace, '{'.to_string()),
+                '}' => Token::new(TokenClass::CloseBrace, '}'.to_string()),
                 ',' => Token::new(TokenClass::Comma, ';'.to_string()),
+                ':' => Token::new(TokenClass::Colon, ':'.to_string()),
                 '\'' => Token::new(TokenClass::Prime, '\''.to_string()),
-                _ => {
-                    throw(UnexpectedEof);
-                    Token::new(TokenClass::Newline, '\n'.to_string())
-                },
+                _ => return Err(UnexpectedEof.with_span(Span::new(start, charstream.position()))),
             };
-            tokens.push(token);
 
-            // Skip comments
-            charstream.skip_comments();
+            // A bracket attached to a value, as in `A[1, 2]`, holds indices rather than
+            // matrix rows, so it continues onto the next line like a parenthesis
+            let is_index = token.check(TokenClass::OpenBracket) && tokens.last().is_some_and(|t: &Token| {
+                let span = t.get_span();
+                (span.end_line(), span.end_col()) == start && matches!(
+                    t.get_class(),
+                    TokenClass::Identifier | TokenClass::CloseParen | TokenClass::CloseBracket | TokenClass::Prime,
+                )
+            });
+
+            match token.get_class() {
+                TokenClass::OpenBracket if is_index => open.push(TokenClass::OpenParen),
+                TokenClass::OpenParen | TokenClass::OpenBracket | TokenClass::OpenBrace => open.push(token.get_class()),
+                TokenClass::CloseParen | TokenClass::CloseBracket | TokenClass::CloseBrace => {
+                    open.pop();
+                },
+                _ => (),
+            }
+
+            tokens.push(Token {
+                span: Span::new(start, charstream.position()),
+                ..token
+            });
         }
 
-        Self {
+        Ok(Self {
             tokens,
             index,
+            delimiters: Vec::new(),
+            unclosed: open.len(),
+        })
+    }
+
+    /// Records that the parser has entered a delimited region, such as a
+    /// parenthetical (`TokenClass::OpenParen`) or a matrix (`TokenClass::OpenBracket`).
+    pub fn open(&mut self, delimiter: TokenClass) {
+        self.delimiters.push(delimiter);
+    }
+
+    /// Records that the parser has left the innermost delimited region.
+    pub fn close(&mut self) {
+        self.delimiters.pop();
+    }
+
+    /// Checks whether or not the innermost delimited region is a matrix.
+    pub fn in_matrix(&self) -> bool {
+        self.delimiters.last() == Some(&TokenClass::OpenBracket)
+    }
+
+    /// Checks whether or not the next token begins a new value inside a matrix.
+    /// 
+    /// This is the case for a sign that is separated from the previous token but
+    /// attached to the following one, as in `[1 -2]`, and for a parenthesis or bracket
+    /// that is separated from the previous token, as in `[1 (2)]` or `[x [2 3]]`.
+    pub fn next_starts_value(&self) -> bool {
+        let (previous, current) = match (
+            self.index.checked_sub(1).and_then(|i| self.tokens.get(i)),
+            self.tokens.get(self.index),
+        ) {
+            (Some(p), Some(c)) => (p.get_span(), c),
+            _ => return false,
+        };
+
+        let span = current.get_span();
+        let separated_before = (previous.end_line(), previous.end_col()) != (span.line(), span.col());
+        let attached_after = match self.tokens.get(self.index + 1) {
+            Some(f) => (span.end_line(), span.end_col()) == (f.get_span().line(), f.get_span().col()),
+            None => false,
+        };
+
+        match current.get_class() {
+            TokenClass::Plus | TokenClass::Minus => separated_before && attached_after,
+            TokenClass::OpenParen | TokenClass::OpenBracket => separated_before,
+            _ => false,
+        }
+    }
+
+    /// Gets the location immediately after the last token in the stream.
+    pub fn eof_span(&self) -> Span {
+        match self.tokens.last() {
+            Some(t) => {
+                let span = t.get_span();
+                let end = (span.end_line(), span.end_col());
+                Span::new(end, end)
+            },
+            None => Span::new((1, 1), (1, 1)),
         }
     }
 
@@ -276,11 +721,18 @@ impl Tokenizer {
         }
     }
 
-    /// Advances the character stream.
-    pub fn next(&mut self) -> Option<Token> {
-        let token = self.peek();
-        self.index += 1;
-        token
+    /// Looks ahead `n` tokens.
+    /// 
+    /// `Self::lookahead(0)` is equivalent to `Self::peek()`.
+    pub fn lookahead(&self, n: usize) -> Option<Token> {
+        self.tokens.get(self.index + n).cloned()
+    }
+
+    /// Looks behind `n` tokens.
+    /// 
+    /// `Self::lookbehind(1)` is the most recently consumed token.
+    pub fn lookbehind(&self, n: usize) -> Option<Token> {
+        self.index.checked_sub(n).and_then(|i| self.tokens.get(i)).cloned()
     }
 
     /// Returns all tokens without consuming the tokenizer.
@@ -288,14 +740,33 @@ impl Tokenizer {
         self.tokens.to_owned()
     }
 
-    /// Checks whether or not the last token is a semicolon.
+    /// Checks whether or not every parenthesis, bracket and brace opened in the
+    /// stream has been closed.
     /// 
-    /// Lines that end with semicolons are not displayed.
-    pub fn chk_silent(&self) -> bool {
-        if self.tokens.len() != 0 {
-            self.tokens.len() != 0 && self.tokens[self.tokens.len() - 1].get_class() == TokenClass::Semicolon
-        } else {
-            true
+    /// Hosts use this to decide whether a statement continues on the next line.
+    pub fn is_complete(&self) -> bool {
+        self.unclosed == 0
+    }
+
+    /// Skips any newlines at the front of the stream.
+    pub fn skip_newlines(&mut self) {
+        while self.peek().is_some_and(|t| t.check(TokenClass::Newline)) {
+            self.index += 1;
+        }
+    }
+
+    /// Gets the class of the next token that is not a newline, without consuming anything.
+    pub fn peek_past_newlines(&self) -> Option<TokenClass> {
+        self.tokens[self.index.min(self.tokens.len())..].iter()
+            .map(|t| t.get_class())
+            .find(|c| *c != TokenClass::Newline)
+    }
+
+    /// Gets the location of the most recently consumed token.
+    pub fn last_span(&self) -> Span {
+        match self.index.checked_sub(1).and_then(|i| self.tokens.get(i)) {
+            Some(t) => t.get_span(),
+            None => Span::new((1, 1), (1, 1)),
         }
     }
 
@@ -309,9 +780,141 @@ impl Tokenizer {
     }
 }
 
+impl Iterator for Tokenizer {
+    type Item = Token;
+
+    /// Advances the token stream.
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek();
+        self.index += 1;
+        token
+    }
+}
+
 #[test]
 fn tokenize_00() {
     let input: String = "x = 1.3\ny = 2.6".to_string();
-    let mut tokenizer = Tokenizer::from(input);
+    let mut tokenizer = Tokenizer::from(input).unwrap();
     println!("Tokens: {:#?}", tokenizer.get_tokens());
-}
\ No newline at end of file
+}
+#[test]
+fn tokenize_01() {
+    let input: String = "x = 1.3\n  y = 2.6".to_string();
+    let tokens = Tokenizer::from(input).unwrap().get_tokens();
+    assert_eq!(tokens[0].get_span(), Span::new((1, 1), (1, 2)));
+    assert_eq!(tokens[2].get_span(), Span::new((1, 5), (1, 8)));
+    assert_eq!(tokens[3].get_class(), TokenClass::Newline);
+    assert_eq!(tokens[4].get_span(), Span::new((2, 3), (2, 4)));
+
+    let input: String = "x = 1.2.3".to_string();
+    let error = Tokenizer::from(input).err().unwrap();
+    assert_eq!(error.kind(), &CouldNotParseNumeric);
+    assert_eq!(error.span(), Some (Span::new((1, 5), (1, 10))));
+}
+#[test]
+fn tokenize_02() {
+    let input: String = "2.*x ./ 1.5.^2".to_string();
+    let classes = Tokenizer::from(input).unwrap().get_tokens().iter().map(|t| t.get_class()).collect::<Vec<TokenClass>>();
+    assert_eq!(classes, vec![
+        TokenClass::Int,
+        TokenClass::DotMultiply,
+        TokenClass::Identifier,
+        TokenClass::DotDivide,
+        TokenClass::Float,
+        TokenClass::DotCaret,
+        TokenClass::Int,
+    ]);
+}
+#[test]
+fn tokenize_03() {
+    let input: String = "a == b != !c <= d && e >= f || g < h > i".to_string();
+    let classes = Tokenizer::from(input).unwrap().get_tokens().iter()
+        .map(|t| t.get_class())
+        .filter(|c| *c != TokenClass::Identifier)
+        .collect::<Vec<TokenClass>>();
+    assert_eq!(classes, vec![
+        TokenClass::Eq,
+        TokenClass::NotEq,
+        TokenClass::Not,
+        TokenClass::LessEq,
+        TokenClass::And,
+        TokenClass::GreaterEq,
+        TokenClass::Or,
+        TokenClass::Less,
+        TokenClass::Greater,
+    ]);
+}
+#[test]
+fn tokenize_04() {
+    let input: String = "x = f(1,\n 2) // comment\n[1 2\n 3 4]".to_string();
+    let tokenizer = Tokenizer::from(input).unwrap();
+    assert!(tokenizer.is_complete());
+
+    let classes = tokenizer.tokens.iter().map(|t| t.get_class()).collect::<Vec<TokenClass>>();
+    assert_eq!(classes.iter().filter(|c| **c == TokenClass::Newline).count(), 2);
+    assert_eq!(classes[classes.len() - 4], TokenClass::Newline);
+
+    assert!(!Tokenizer::from("A = [1 2\n".to_string()).unwrap().is_complete());
+}
+#[test]
+fn tokenize_05() {
+    let input: String = "6.022e23 1.5E-9 2e x 4.7k 10u 2.2M 1e3m 5kg 0x1F 0b1010_0101 1_000_000 2.5_5 1T".to_string();
+    let tokens = Tokenizer::from(input).unwrap().get_tokens();
+    let values = tokens.iter().map(|t| t.get_value()).collect::<Vec<String>>();
+    assert_eq!(values, vec![
+        "6.022e23", "1.5E-9", "2", "e", "x", "4700", "0.00001", "2200000", "1e3", "m", "5", "kg", "31", "165", "1000000", "2.55", "1", "T",
+    ]);
+    assert_eq!(tokens[5].get_class(), TokenClass::Float);
+    assert_eq!(tokens[12].get_class(), TokenClass::Int);
+
+    let tokens = Tokenizer::from("0xFFFFFFFFFFFFFFFFF".to_string()).unwrap().get_tokens();
+    assert_eq!(tokens[0].get_class(), TokenClass::Int);
+    assert_eq!(tokens[0].get_value(), "295147905179352825855");
+}
+#[test]
+fn tokenize_06() {
+    let input: String = "ω_n = 2×π·fₙ ÷ θ − 1 ≤ 2".to_string();
+    let tokens = Tokenizer::from(input).unwrap().get_tokens();
+    let values = tokens.iter().map(|t| t.get_value()).collect::<Vec<String>>();
+    assert_eq!(values, vec!["ω_n", "=", "2", "*", "π", "*", "fₙ", "/", "θ", "-", "1", "<=", "2"]);
+
+    // Spans count characters, not bytes
+    assert_eq!(tokens[4].get_span(), Span::new((1, 9), (1, 10)));
+}
+
+#[test]
+fn tokenize_07() {
+    let input: String = "4i + 2.5j - 1e3i + 2in".to_string();
+    let tokens = Tokenizer::from(input).unwrap().get_tokens();
+    let classes = tokens.iter().map(|t| t.get_class()).collect::<Vec<TokenClass>>();
+    assert_eq!(classes, vec![
+        TokenClass::Imaginary,
+        TokenClass::Plus,
+        TokenClass::Imaginary,
+        TokenClass::Minus,
+        TokenClass::Imaginary,
+        TokenClass::Plus,
+        TokenClass::Int,
+        TokenClass::In,
+    ]);
+    assert_eq!(tokens[2].get_value(), "2.5");
+}
+
+#[test]
+fn tokenize_08() {
+    let input: String = "9.81 ± 0.02 + 1 +- 0.1".to_string();
+    let tokens = Tokenizer::from(input).unwrap().get_tokens();
+    let values = tokens.iter().map(|t| t.get_value()).collect::<Vec<String>>();
+    assert_eq!(values, vec!["9.81", "±", "0.02", "+", "1", "±", "0.1"]);
+    assert_eq!(tokens[5].get_class(), TokenClass::PlusMinus);
+}
+#[test]
+fn tokenize_09() {
+    let input: String = "A\\b".to_string();
+    let classes = Tokenizer::from(input).unwrap().get_tokens().iter().map(|t| t.get_class()).collect::<Vec<TokenClass>>();
+    assert_eq!(classes, vec![
+        TokenClass::Identifier,
+        TokenClass::Backslash,
+        TokenClass::Identifier,
+    ]);
+}
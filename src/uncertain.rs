@@ -0,0 +1,222 @@
+//! Provides uncertain values, with first-order propagation of uncertainty.
+
+use std::{
+    fmt::{
+        self,
+        Display,
+        Formatter,
+    },
+    ops::{
+        Add,
+        Sub,
+        Mul,
+        Div,
+        Rem,
+        Neg,
+    },
+    sync::atomic::{
+        AtomicUsize,
+        Ordering,
+    },
+};
+
+use num_traits::{
+    Num,
+    One,
+    ParseFloatError,
+    Zero,
+};
+
+/// Counts the independent sources of uncertainty created so far.
+static SOURCES: AtomicUsize = AtomicUsize::new(0);
+
+/// Holds an uncertain value, like `9.81 ± 0.02`.
+///
+/// Each value created with `±` is an independent source of uncertainty.
+/// Values computed from them keep their sensitivity (partial derivative)
+/// to each source, so repeated variables are correlated and `x - x` is
+/// exactly `0`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Uncertain {
+    nominal: f64,
+    sensitivities: Vec<(usize, f64)>,
+}
+
+impl Uncertain {
+    /// Constructs a new, independent uncertain value with the given standard deviation.
+    pub fn new(nominal: f64, deviation: f64) -> Self {
+        let source = SOURCES.fetch_add(1, Ordering::Relaxed);
+
+        Self {
+            nominal,
+            sensitivities: vec![(source, deviation.abs())],
+        }
+    }
+
+    /// Constructs an exact value, with no uncertainty.
+    pub fn exact(nominal: f64) -> Self {
+        Self {
+            nominal,
+            sensitivities: Vec::new(),
+        }
+    }
+
+    /// Gets the nominal value.
+    pub fn nominal(&self) -> f64 {
+        self.nominal
+    }
+
+    /// Gets the standard deviation.
+    pub fn deviation(&self) -> f64 {
+        self.sensitivities.iter().map(|(_, d)| d*d).sum::<f64>().sqrt()
+    }
+
+    /// Combines two uncertain values into a new nominal value, given the
+    /// partial derivatives of the result with respect to each of them.
+    fn combine(&self, other: &Self, nominal: f64, dx: f64, dy: f64) -> Self {
+        let mut sensitivities = self.sensitivities.iter().map(|(s, d)| (*s, d*dx)).collect::<Vec<(usize, f64)>>();
+        for (source, d) in &other.sensitivities {
+            match sensitivities.iter_mut().find(|(s, _)| s == source) {
+                Some((_, e)) => *e += d*dy,
+                None => sensitivities.push((*source, d*dy)),
+            }
+        }
+
+        Self {
+            nominal,
+            sensitivities,
+        }
+    }
+
+    /// Applies a function to this value, given the derivative of the function at this value.
+    pub fn map(&self, nominal: f64, derivative: f64) -> Self {
+        self.combine(&Self::exact(0.0), nominal, derivative, 0.0)
+    }
+
+    /// Computes the square root of this value.
+    pub fn sqrt(&self) -> Self {
+        let root = self.nominal.sqrt();
+        self.map(root, 0.5/root)
+    }
+
+    /// Computes the sine of this value.
+    pub fn sin(&self) -> Self {
+        self.map(self.nominal.sin(), self.nominal.cos())
+    }
+
+    /// Computes the cosine of this value.
+    pub fn cos(&self) -> Self {
+        self.map(self.nominal.cos(), -self.nominal.sin())
+    }
+
+    /// Computes the absolute value of this value.
+    pub fn abs(&self) -> Self {
+        self.map(self.nominal.abs(), self.nominal.signum())
+    }
+
+    /// Raises this value to an uncertain power.
+    pub fn pow(&self, other: &Self) -> Self {
+        let (x, y) = (self.nominal, other.nominal);
+        let nominal = x.powf(y);
+
+        // The power only depends on the exponent if the exponent is uncertain,
+        // which keeps `(-2 ± 0.1)^2` well-defined
+        let dy = if other.sensitivities.is_empty() {
+            0.0
+        } else {
+            nominal*x.ln()
+        };
+
+        self.combine(other, nominal, y*x.powf(y - 1.0), dy)
+    }
+}
+
+impl Add for Uncertain {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.combine(&other, self.nominal + other.nominal, 1.0, 1.0)
+    }
+}
+
+impl Sub for Uncertain {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.combine(&other, self.nominal - other.nominal, 1.0, -1.0)
+    }
+}
+
+impl Mul for Uncertain {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.combine(&other, self.nominal*other.nominal, other.nominal, self.nominal)
+    }
+}
+
+impl Div for Uncertain {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        let (x, y) = (self.nominal, other.nominal);
+        self.combine(&other, x/y, 1.0/y, -x/(y*y))
+    }
+}
+
+impl Rem for Uncertain {
+    type Output = Self;
+
+    fn rem(self, other: Self) -> Self {
+        let (x, y) = (self.nominal, other.nominal);
+        self.combine(&other, x % y, 1.0, -(x/y).trunc())
+    }
+}
+
+impl Neg for Uncertain {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.map(-self.nominal, -1.0)
+    }
+}
+
+impl Zero for Uncertain {
+    fn zero() -> Self {
+        Self::exact(0.0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.nominal == 0.0 && self.deviation() == 0.0
+    }
+}
+
+impl One for Uncertain {
+    fn one() -> Self {
+        Self::exact(1.0)
+    }
+}
+
+/// Allows uncertain values to be stored in (and inverted as) matrices.
+impl Num for Uncertain {
+    type FromStrRadixErr = ParseFloatError;
+
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseFloatError> {
+        f64::from_str_radix(s, radix).map(Self::exact)
+    }
+}
+
+/// Displays uncertain values like `3.142 ± 0.004`, rounding the nominal value
+/// to the first significant digit of the standard deviation.
+impl Display for Uncertain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let deviation = self.deviation();
+
+        if deviation == 0.0 || !deviation.is_finite() {
+            return write!(f, "{} ± {}", self.nominal, deviation);
+        }
+
+        let decimals = (-deviation.log10().floor()).max(0.0) as usize;
+        write!(f, "{:.*} ± {:.*}", decimals, self.nominal, decimals, deviation)
+    }
+}
@@ -0,0 +1,230 @@
+//! Provides physical units for Elemental quantities.
+
+use std::fmt::{
+    self,
+    Display,
+    Formatter,
+};
+
+use crate::tokenizer::si_prefix;
+
+/// Holds the dimension of a unit, as powers of the SI base units
+/// (in order: m, kg, s, A, K, mol, cd).
+pub type Dimension = [i32; 7];
+
+/// Holds a unit of measurement.
+///
+/// A unit is a product of named factors (like `kN` and `m`) with integer
+/// powers, which together are `scale` times a coherent SI unit of the given
+/// dimension.  A unit with no factors is dimensionless.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Unit {
+    factors: Vec<(String, i32)>,
+    scale: f64,
+    dimension: Dimension,
+}
+
+/// Named units, with their size in SI base units and their dimension,
+/// and whether or not they accept SI prefixes.
+const UNITS: [(&str, f64, Dimension, bool); 33] = [
+    ("m", 1.0, [1, 0, 0, 0, 0, 0, 0], true),
+    ("g", 1e-3, [0, 1, 0, 0, 0, 0, 0], true),
+    ("s", 1.0, [0, 0, 1, 0, 0, 0, 0], true),
+    ("A", 1.0, [0, 0, 0, 1, 0, 0, 0], true),
+    ("K", 1.0, [0, 0, 0, 0, 1, 0, 0], true),
+    ("mol", 1.0, [0, 0, 0, 0, 0, 1, 0], true),
+    ("cd", 1.0, [0, 0, 0, 0, 0, 0, 1], true),
+    ("Hz", 1.0, [0, 0, -1, 0, 0, 0, 0], true),
+    ("N", 1.0, [1, 1, -2, 0, 0, 0, 0], true),
+    ("Pa", 1.0, [-1, 1, -2, 0, 0, 0, 0], true),
+    ("J", 1.0, [2, 1, -2, 0, 0, 0, 0], true),
+    ("W", 1.0, [2, 1, -3, 0, 0, 0, 0], true),
+    ("C", 1.0, [0, 0, 1, 1, 0, 0, 0], true),
+    ("V", 1.0, [2, 1, -3, -1, 0, 0, 0], true),
+    ("Ω", 1.0, [2, 1, -3, -2, 0, 0, 0], true),
+    ("ohm", 1.0, [2, 1, -3, -2, 0, 0, 0], true),
+    ("S", 1.0, [-2, -1, 3, 2, 0, 0, 0], true),
+    ("F", 1.0, [-2, -1, 4, 2, 0, 0, 0], true),
+    ("H", 1.0, [2, 1, -2, -2, 0, 0, 0], true),
+    ("Wb", 1.0, [2, 1, -2, -1, 0, 0, 0], true),
+    ("T", 1.0, [0, 1, -2, -1, 0, 0, 0], true),
+    ("L", 1e-3, [3, 0, 0, 0, 0, 0, 0], true),
+    ("eV", 1.602176634e-19, [2, 1, -2, 0, 0, 0, 0], true),
+    ("bar", 1e5, [-1, 1, -2, 0, 0, 0, 0], true),
+    ("min", 60.0, [0, 0, 1, 0, 0, 0, 0], false),
+    ("h", 3600.0, [0, 0, 1, 0, 0, 0, 0], false),
+    ("inch", 0.0254, [1, 0, 0, 0, 0, 0, 0], false),
+    ("ft", 0.3048, [1, 0, 0, 0, 0, 0, 0], false),
+    ("mi", 1609.344, [1, 0, 0, 0, 0, 0, 0], false),
+    ("lb", 0.45359237, [0, 1, 0, 0, 0, 0, 0], false),
+    ("lbf", 4.4482216152605, [1, 1, -2, 0, 0, 0, 0], false),
+    ("psi", 6894.757293168361, [-1, 1, -2, 0, 0, 0, 0], false),
+    ("atm", 101325.0, [-1, 1, -2, 0, 0, 0, 0], false),
+];
+
+/// Base and coherent derived units, which can replace products of other units
+/// with the same dimension.
+const DERIVED: [&str; 19] = ["m", "g", "s", "A", "K", "mol", "cd", "N", "Pa", "J", "W", "C", "V", "Ω", "S", "F", "H", "Wb", "T"];
+
+/// SI prefixes used when naming the result of a product of units.
+const PREFIXES: &str = "fpnµmkMGTP";
+
+
+/// Gets the power of ten represented by a prefix of a unit.
+/// 
+/// Units accept every SI prefix that numbers do, and also `c` (centi).
+fn prefix(c: char) -> Option<i32> {
+    match c {
+        'c' => Some (-2),
+        _ => si_prefix(c),
+    }
+}
+
+impl Unit {
+    /// Constructs the unit of dimensionless numbers.
+    pub fn none() -> Self {
+        Self {
+            factors: Vec::new(),
+            scale: 1.0,
+            dimension: [0; 7],
+        }
+    }
+
+    /// Looks up a unit by name, which may begin with an SI prefix (as in `kN`).
+    ///
+    /// Returns `None` if the name is not a unit.
+    pub fn parse(name: &str) -> Option<Self> {
+        let named = |n: &str| UNITS.iter().find(|u| u.0 == n);
+
+        let (scale, dimension) = match named(name) {
+            Some((_, s, d, _)) => (*s, *d),
+            None => {
+                let mut chars = name.chars();
+                let power = chars.next().and_then(prefix)?;
+                match named(chars.as_str()) {
+                    Some((_, s, d, true)) => (s*10f64.powi(power), *d),
+                    _ => return None,
+                }
+            },
+        };
+
+        Some (Self {
+            factors: vec![(name.to_string(), 1)],
+            scale,
+            dimension,
+        })
+    }
+
+    /// Gets the size of this unit in SI base units.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Gets the dimension of this unit.
+    pub fn dimension(&self) -> Dimension {
+        self.dimension
+    }
+
+    /// Checks whether or not this unit is dimensionless.
+    pub fn is_dimensionless(&self) -> bool {
+        self.dimension == [0; 7]
+    }
+
+    /// Multiplies this unit by another.
+    pub fn multiply(&self, other: &Unit) -> Self {
+        let mut factors = self.factors.to_owned();
+        for (name, power) in &other.factors {
+            match factors.iter_mut().find(|(n, _)| n == name) {
+                Some((_, p)) => *p += power,
+                None => factors.push((name.to_owned(), *power)),
+            }
+        }
+        factors.retain(|(_, p)| *p != 0);
+
+        let mut dimension = self.dimension;
+        for (d, o) in dimension.iter_mut().zip(other.dimension) {
+            *d += o;
+        }
+
+        Self {
+            factors,
+            scale: self.scale*other.scale,
+            dimension,
+        }
+    }
+
+    /// Divides this unit by another.
+    pub fn divide(&self, other: &Unit) -> Self {
+        self.multiply(&other.pow(-1))
+    }
+
+    /// Raises this unit to an integer power.
+    pub fn pow(&self, n: i32) -> Self {
+        Self {
+            factors: self.factors.iter().filter(|_| n != 0).map(|(f, p)| (f.to_owned(), p*n)).collect(),
+            scale: self.scale.powi(n),
+            dimension: self.dimension.map(|d| d*n),
+        }
+    }
+
+    /// Takes the `n`th root of this unit, if every factor has a power divisible by `n`.
+    pub fn root(&self, n: i32) -> Option<Self> {
+        if self.factors.iter().any(|(_, p)| p % n != 0) {
+            return None;
+        }
+
+        Some (Self {
+            factors: self.factors.iter().map(|(f, p)| (f.to_owned(), p/n)).collect(),
+            scale: self.scale.powf(1.0/n as f64),
+            dimension: self.dimension.map(|d| d/n),
+        })
+    }
+
+    /// Replaces a product of several units with the (possibly prefixed) base or
+    /// derived unit of the same size, if there is one.
+    /// 
+    /// For example, `kg·m/s^2` becomes `N` and `V/kΩ` becomes `mA`.
+    pub fn derived(self) -> Self {
+        if self.factors.len() < 2 {
+            return self;
+        }
+
+        let candidates = DERIVED.iter().flat_map(|n| {
+            PREFIXES.chars().map(move |p| format!("{}{}", p, n)).chain(std::iter::once(n.to_string()))
+        });
+
+        for name in candidates {
+            if let Some(u) = Self::parse(&name) {
+                if u.dimension == self.dimension && (u.scale - self.scale).abs() <= 1e-12*self.scale {
+                    return u;
+                }
+            }
+        }
+
+        self
+    }
+}
+
+/// Displays units like `kg·m/s^2`.
+impl Display for Unit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let factor = |(name, power): &(String, i32)| match power.abs() {
+            1 => name.to_owned(),
+            p => format!("{}^{}", name, p),
+        };
+
+        let numerator = self.factors.iter().filter(|(_, p)| *p > 0).map(factor).collect::<Vec<String>>();
+        let denominator = self.factors.iter().filter(|(_, p)| *p < 0).map(factor).collect::<Vec<String>>();
+
+        let numerator = match numerator.len() {
+            0 => "1".to_string(),
+            _ => numerator.join("·"),
+        };
+
+        match denominator.len() {
+            0 => write!(f, "{}", numerator),
+            1 => write!(f, "{}/{}", numerator, denominator[0]),
+            _ => write!(f, "{}/({})", numerator, denominator.join("·")),
+        }
+    }
+}